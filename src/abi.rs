@@ -0,0 +1,10 @@
+use ethers::abi::{Function, HumanReadableParser};
+
+/// Parses a human-readable function signature such as
+/// `function transfer(address to, uint256 amount) returns (bool)`.
+///
+/// Only used with the static signatures in this crate, so a parse failure is a bug.
+pub(crate) fn function(signature: &str) -> Function {
+    HumanReadableParser::parse_function(signature)
+        .unwrap_or_else(|err| panic!("invalid function signature {}: {}", signature, err))
+}
@@ -0,0 +1,248 @@
+use crate::{
+    client::FrameClient,
+    error::{FrameError, Result},
+};
+use ethers::{
+    middleware::Middleware,
+    providers::JsonRpcClient,
+    types::{
+        transaction::eip2718::TypedTransaction,
+        transaction::eip2930::{AccessList, Eip2930TransactionRequest},
+        Address, Bytes, TransactionRequest, H256, U256,
+    },
+};
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Sends an EIP-2930 (type 1) transaction with an access list through Frame.
+    ///
+    /// Declaring the addresses and storage slots a transaction touches makes the first access
+    /// to each of them cheaper, which can lower the cost of calls that touch many slots. The
+    /// list for a call can be generated with [`FrameClient::create_access_list`]. Like the other
+    /// sending methods, this waits for the receipt.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` that signs and sends the transaction.
+    /// - `to`: The `Address` of the recipient or contract.
+    /// - `value`: The amount of the gas token to send along, in Wei.
+    /// - `data`: The calldata, empty for a plain transfer.
+    /// - `access_list`: The addresses and storage keys to declare.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, Bytes, TransactionRequest, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///     let to: Address = "0x...".parse()?;
+    ///     let data: Bytes = "0x...".parse()?;
+    ///
+    ///     let call = TransactionRequest::new().from(from).to(to).data(data.clone());
+    ///     let (access_list, _) = client.create_access_list(&call).await?;
+    ///     let tx_hash = client
+    ///         .send_access_list_transaction(from, to, U256::zero(), data, access_list)
+    ///         .await?;
+    ///     println!("Transaction hash: {:?}", tx_hash);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    /// A chain that doesn't support typed transactions rejects it with
+    /// `FrameError::RpcError`. If the user declines the transaction in Frame, the error is
+    /// `FrameError::UserRejected`.
+    pub async fn send_access_list_transaction(
+        &self,
+        from: Address,
+        to: Address,
+        value: U256,
+        data: Bytes,
+        access_list: AccessList,
+    ) -> Result<H256> {
+        let tx = TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .value(value)
+            .data(data)
+            .with_access_list(access_list);
+        self.send_and_wait(tx).await
+    }
+
+    /// Generates the access list of a call with `eth_createAccessList`.
+    ///
+    /// # Parameters
+    /// - `tx`: The `TransactionRequest` to generate the access list for.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `AccessList` of the addresses and storage
+    /// keys the call touches, and the gas the call uses with that list attached.
+    ///
+    /// # Errors
+    /// Returns `FrameError::ExecutionReverted` if the call reverts, and
+    /// `FrameError::Unsupported` if the node doesn't implement `eth_createAccessList`.
+    pub async fn create_access_list(&self, tx: &TransactionRequest) -> Result<(AccessList, U256)> {
+        let tx: TypedTransaction = tx.clone().into();
+        self.generate_access_list(&tx).await
+    }
+
+    /// Attaches the access list generated for `tx` if it calls a contract and has no access list
+    /// yet, turning a legacy transaction into an EIP-2930 one.
+    ///
+    /// This is best effort: if the node doesn't implement `eth_createAccessList` or the call
+    /// reverts, `tx` is left as is and the revert surfaces when its gas is estimated.
+    pub(crate) async fn attach_access_list(&self, tx: &mut TypedTransaction) {
+        let has_access_list = tx.access_list().is_some_and(|list| !list.0.is_empty());
+        let calls_contract = tx.to().is_some() && tx.data().is_some_and(|data| !data.is_empty());
+        if has_access_list || !calls_contract {
+            return;
+        }
+        let Ok((access_list, _)) = self.generate_access_list(tx).await else {
+            return;
+        };
+        if access_list.0.is_empty() {
+            return;
+        }
+
+        if let TypedTransaction::Legacy(legacy) = tx {
+            let legacy = legacy.clone();
+            *tx = TypedTransaction::Eip2930(Eip2930TransactionRequest::new(legacy, access_list));
+        } else {
+            tx.set_access_list(access_list);
+        }
+    }
+
+    async fn generate_access_list(&self, tx: &TypedTransaction) -> Result<(AccessList, U256)> {
+        match self.provider.create_access_list(tx, None).await {
+            Ok(generated) => Ok((generated.access_list, generated.gas_used)),
+            Err(err) => match FrameError::from_revert(err) {
+                err if err.is_method_not_found() => {
+                    Err(FrameError::Unsupported("eth_createAccessList".to_string()))
+                }
+                err => Err(err),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::push_mined;
+    use ethers::{
+        providers::{JsonRpcError, MockResponse},
+        types::transaction::eip2930::{AccessListItem, AccessListWithGasUsed},
+    };
+
+    #[tokio::test]
+    async fn test_create_access_list() {
+        let (client, mock) = FrameClient::mocked();
+        let access_list = AccessList(vec![AccessListItem {
+            address: Address::random(),
+            storage_keys: vec![H256::random()],
+        }]);
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: -32601,
+            message: "the method eth_createAccessList does not exist".to_string(),
+            data: None,
+        }));
+        mock.push(AccessListWithGasUsed {
+            access_list: access_list.clone(),
+            gas_used: U256::from(30_000),
+        })
+        .unwrap();
+
+        let tx = TransactionRequest::new().to(Address::random());
+        assert_eq!(
+            client.create_access_list(&tx).await.unwrap(),
+            (access_list, U256::from(30_000))
+        );
+        let err = client.create_access_list(&tx).await.unwrap_err();
+        assert!(
+            matches!(&err, FrameError::Unsupported(method) if method == "eth_createAccessList")
+        );
+        assert!(err.is_method_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_send_access_list_transaction() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let access_list = AccessList(vec![AccessListItem {
+            address: to,
+            storage_keys: vec![H256::zero()],
+        }]);
+        let tx_hash = H256::random();
+        push_mined(&mock, tx_hash);
+        mock.push(U256::from(30_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+
+        client
+            .send_access_list_transaction(from, to, U256::zero(), Bytes::new(), access_list.clone())
+            .await
+            .unwrap();
+
+        let estimated: TypedTransaction = TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .value(0)
+            .data(Bytes::new())
+            .gas_price(1)
+            .with_access_list(access_list)
+            .into();
+        let mut sent = estimated.clone();
+        sent.set_gas(30_000);
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+        mock.assert_request("eth_estimateGas", [estimated]).unwrap();
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_auto_access_list() {
+        let (mut client, mock) = FrameClient::mocked();
+        client.auto_access_list = true;
+        let (from, to) = (Address::random(), Address::random());
+        let access_list = AccessList(vec![AccessListItem {
+            address: Address::random(),
+            storage_keys: vec![H256::zero()],
+        }]);
+        let tx_hash = H256::random();
+        push_mined(&mock, tx_hash);
+        mock.push(U256::from(50_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        mock.push(AccessListWithGasUsed {
+            access_list: access_list.clone(),
+            gas_used: U256::from(50_000),
+        })
+        .unwrap();
+
+        let data = Bytes::from_static(&[0xd0, 0xe3, 0x0d, 0xb0]);
+        client
+            .send_transaction_raw(from, to, data.clone(), None)
+            .await
+            .unwrap();
+
+        let call: TypedTransaction = TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .data(data)
+            .into();
+        mock.assert_request("eth_createAccessList", (&call, "latest"))
+            .unwrap();
+        let TypedTransaction::Legacy(legacy) = call else {
+            unreachable!()
+        };
+        let estimated: TypedTransaction =
+            Eip2930TransactionRequest::new(legacy.gas_price(1), access_list).into();
+        let mut sent = estimated.clone();
+        sent.set_gas(50_000);
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+        mock.assert_request("eth_estimateGas", [estimated]).unwrap();
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+    }
+}
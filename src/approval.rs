@@ -0,0 +1,138 @@
+use crate::{
+    client::FrameClient,
+    error::{FrameError, Result},
+};
+use ethers::{providers::JsonRpcClient, types::transaction::eip2718::TypedTransaction};
+use futures::future::BoxFuture;
+use std::{fmt, sync::Arc};
+
+/// The decision of a [`BeforeSendHook`] on a transaction about to be sent.
+// Returned once per send, so keeping `Modify` unboxed costs nothing worth the clumsier API.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Approval {
+    /// Sends the transaction as it is.
+    Approve,
+    /// Sends this transaction instead, for example with adjusted fees.
+    Modify(TypedTransaction),
+    /// Aborts the send with `FrameError::SendRejected` and this reason.
+    Reject(String),
+}
+
+/// A callback invoked with every transaction before it is sent to Frame, set with
+/// [`FrameClientBuilder::on_before_send`](crate::builder::FrameClientBuilder::on_before_send).
+///
+/// An error returned by the hook aborts the send and is returned as is.
+pub type BeforeSendHook =
+    Arc<dyn Fn(&TypedTransaction) -> BoxFuture<'static, Result<Approval>> + Send + Sync>;
+
+/// The optional [`BeforeSendHook`] of a client.
+#[derive(Clone, Default)]
+pub(crate) struct BeforeSend(Option<BeforeSendHook>);
+
+impl BeforeSend {
+    pub(crate) fn new(hook: BeforeSendHook) -> Self {
+        Self(Some(hook))
+    }
+}
+
+impl fmt::Debug for BeforeSend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(_) => write!(f, "BeforeSend(Some(..))"),
+            None => write!(f, "BeforeSend(None)"),
+        }
+    }
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Passes `tx` to the before-send hook, if one is set, and applies its decision.
+    pub(crate) async fn approve_send(&self, tx: &mut TypedTransaction) -> Result<()> {
+        let Some(hook) = &self.before_send.0 else {
+            return Ok(());
+        };
+        match hook(tx).await? {
+            Approval::Approve => Ok(()),
+            Approval::Modify(modified) => {
+                *tx = modified;
+                Ok(())
+            }
+            Approval::Reject(reason) => Err(FrameError::SendRejected(reason)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blob::BlobTxRequest, nonce::NonceCache};
+    use ethers::types::{Address, TransactionRequest, H256, U256};
+    use futures::FutureExt;
+
+    #[tokio::test]
+    async fn test_before_send_modify() {
+        let (mut client, mock) = FrameClient::mocked();
+        client.before_send = BeforeSend::new(Arc::new(|tx: &TypedTransaction| {
+            let mut tx = tx.clone();
+            tx.set_gas_price(U256::from(5));
+            async move { Ok(Approval::Modify(tx)) }.boxed()
+        }));
+        let tx_hash = H256::random();
+        mock.push(tx_hash).unwrap();
+
+        let tx = TransactionRequest::new()
+            .from(Address::random())
+            .to(Address::random())
+            .value(1)
+            .gas(21_000)
+            .gas_price(1);
+        let pending = client.send_pending(tx.clone()).await.unwrap();
+        assert_eq!(pending.hash(), tx_hash);
+
+        let sent: TypedTransaction = tx.gas_price(5).into();
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_before_send_reject_keeps_nonce() {
+        let (mut client, mock) = FrameClient::mocked();
+        client.nonces = Some(NonceCache::default());
+        client.before_send = BeforeSend::new(Arc::new(|_: &TypedTransaction| {
+            async { Ok(Approval::Reject("recipient not allowed".to_string())) }.boxed()
+        }));
+
+        let tx = TransactionRequest::new()
+            .from(Address::random())
+            .to(Address::random());
+        let err = client.send_pending(tx).await.err().unwrap();
+        assert!(
+            matches!(err, FrameError::SendRejected(reason) if reason == "recipient not allowed")
+        );
+        // The send was aborted before a nonce was reserved.
+        assert!(mock.assert_request("eth_chainId", ()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_before_send_sees_blob_transactions() {
+        let (mut client, mock) = FrameClient::mocked();
+        let inbox = Address::random();
+        client.before_send = BeforeSend::new(Arc::new(move |tx: &TypedTransaction| {
+            let approval = match tx.to_addr() {
+                Some(to) if *to == inbox => Approval::Reject("blobs are disabled".to_string()),
+                _ => Approval::Approve,
+            };
+            async move { Ok(approval) }.boxed()
+        }));
+
+        let mut versioned_hash = H256::random();
+        versioned_hash.0[0] = crate::blob::VERSIONED_HASH_VERSION_KZG;
+        let tx = BlobTxRequest {
+            to: inbox,
+            blob_versioned_hashes: vec![versioned_hash],
+            ..Default::default()
+        };
+        let err = client.send_blob_transaction(tx).await.unwrap_err();
+        assert!(matches!(err, FrameError::SendRejected(reason) if reason == "blobs are disabled"));
+        assert!(mock.assert_request("eth_blobBaseFee", ()).is_err());
+    }
+}
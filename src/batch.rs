@@ -0,0 +1,200 @@
+use crate::{
+    client::FrameClient,
+    error::{bail, FrameError, Result},
+    types::BatchMode,
+};
+use ethers::{
+    providers::JsonRpcClient,
+    types::{Address, TransactionRequest, H256, U256},
+};
+use std::collections::HashMap;
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Sends a batch of transactions through Frame that must land in order, such as a setup,
+    /// an approval and an execution.
+    ///
+    /// With `BatchMode::Sequential`, each transaction is only submitted once the previous one
+    /// was mined, so a later transaction can rely on the effects of an earlier one, also in
+    /// gas estimation. With `BatchMode::Pipelined`, the transactions get consecutive nonces
+    /// and are submitted back-to-back; they are mined in order, but this returns before they
+    /// are. Nonces set on the transactions are kept. If the client manages nonces, the
+    /// transactions take theirs from its cache; otherwise they start at the pending
+    /// transaction count of their sender.
+    ///
+    /// The batch stops at the first transaction that fails, including when the user rejects
+    /// it in Frame; the transactions after it are never sent.
+    ///
+    /// # Parameters
+    /// - `txs`: The transactions to send, in order. Each must have its `from` address set.
+    /// - `mode`: Whether to wait for each transaction before sending the next one.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the hashes of the transactions, in order.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, Bytes, TransactionRequest, U256};
+    /// use frame_rs::{client::FrameClient, error::FrameError, types::BatchMode};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///     let (token, vault): (Address, Address) = ("0x...".parse()?, "0x...".parse()?);
+    ///     let (approve, deposit): (Bytes, Bytes) = ("0x...".parse()?, "0x...".parse()?);
+    ///
+    ///     let txs = vec![
+    ///         TransactionRequest::new().from(from).to(token).data(approve),
+    ///         TransactionRequest::new().from(from).to(vault).data(deposit),
+    ///     ];
+    ///     match client.send_batch(txs, BatchMode::Sequential).await {
+    ///         Ok(hashes) => println!("Mined {:?}", hashes),
+    ///         Err(FrameError::BatchFailed { index, submitted, source }) => {
+    ///             println!("Tx {} failed after {:?} were mined: {}", index, submitted, source)
+    ///         }
+    ///         Err(err) => return Err(err.into()),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `FrameError::BatchFailed` if a transaction fails to be sent or, in sequential
+    /// mode, reverts or gets no receipt. The error holds the index of the failed transaction,
+    /// the hashes of the transactions submitted before it, which are mined in sequential mode,
+    /// and the underlying error, e.g. `FrameError::UserRejected`. Returns an error before
+    /// anything is sent if a transaction has no `from` address or the nonces cannot be fetched.
+    pub async fn send_batch(
+        &self,
+        mut txs: Vec<TransactionRequest>,
+        mode: BatchMode,
+    ) -> Result<Vec<H256>> {
+        let mut senders = Vec::with_capacity(txs.len());
+        for (index, tx) in txs.iter().enumerate() {
+            match tx.from {
+                Some(from) => senders.push(from),
+                None => bail!("Transaction {} of the batch has no `from` address", index),
+            }
+        }
+        if mode == BatchMode::Pipelined && self.nonces.is_none() {
+            self.assign_nonces(&mut txs, &senders).await?;
+        }
+
+        let mut submitted = Vec::with_capacity(txs.len());
+        for (index, tx) in txs.into_iter().enumerate() {
+            let sent = match mode {
+                BatchMode::Sequential => self
+                    .send_and_wait_for_receipt(tx)
+                    .await
+                    .map(|receipt| receipt.transaction_hash),
+                BatchMode::Pipelined => self.send_pending(tx).await.map(|pending| pending.hash()),
+            };
+            match sent {
+                Ok(tx_hash) => submitted.push(tx_hash),
+                Err(err) => {
+                    return Err(FrameError::BatchFailed {
+                        index,
+                        submitted,
+                        source: Box::new(err),
+                    })
+                }
+            }
+        }
+        Ok(submitted)
+    }
+
+    /// Sets consecutive nonces on the transactions without one, per sender, starting at the
+    /// sender's pending transaction count.
+    async fn assign_nonces(
+        &self,
+        txs: &mut [TransactionRequest],
+        senders: &[Address],
+    ) -> Result<()> {
+        let mut next: HashMap<Address, U256> = HashMap::new();
+        for (tx, from) in txs.iter_mut().zip(senders) {
+            if tx.nonce.is_some() {
+                continue;
+            }
+            let nonce = match next.get(from) {
+                Some(nonce) => *nonce,
+                None => self.get_pending_nonce(*from).await?,
+            };
+            next.insert(*from, nonce + 1);
+            tx.nonce = Some(nonce);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::push_mined;
+    use ethers::{
+        providers::{JsonRpcError, MockResponse},
+        types::transaction::eip2718::TypedTransaction,
+    };
+
+    #[tokio::test]
+    async fn test_sequential_batch_stops_at_rejection() {
+        let (client, mock) = FrameClient::mocked();
+        let from = Address::random();
+        let first = H256::random();
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 4001,
+            message: "User rejected the request".to_string(),
+            data: None,
+        }));
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        push_mined(&mock, first);
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+
+        let txs = (0..3)
+            .map(|_| TransactionRequest::new().from(from).to(Address::random()))
+            .collect();
+        let err = client
+            .send_batch(txs, BatchMode::Sequential)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FrameError::BatchFailed { index: 1, submitted, source }
+                if submitted == [first] && source.is_user_rejected()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_pipelined_batch_assigns_consecutive_nonces() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let hashes = [H256::random(), H256::random()];
+        for tx_hash in hashes.iter().rev() {
+            mock.push(*tx_hash).unwrap();
+            mock.push(U256::from(21_000)).unwrap();
+            mock.push(U256::from(1)).unwrap();
+        }
+        mock.push(U256::from(4)).unwrap();
+
+        let txs = vec![TransactionRequest::new().from(from).to(to); 2];
+        let sent = client.send_batch(txs, BatchMode::Pipelined).await.unwrap();
+        assert_eq!(sent, hashes);
+
+        mock.assert_request("eth_getTransactionCount", (from, "pending"))
+            .unwrap();
+        for nonce in [4, 5] {
+            let estimated: TypedTransaction = TransactionRequest::new()
+                .from(from)
+                .to(to)
+                .nonce(nonce)
+                .gas_price(1)
+                .into();
+            let mut sent = estimated.clone();
+            sent.set_gas(21_000);
+            mock.assert_request("eth_gasPrice", ()).unwrap();
+            mock.assert_request("eth_estimateGas", [estimated]).unwrap();
+            mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+        }
+    }
+}
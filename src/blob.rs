@@ -0,0 +1,460 @@
+use crate::{
+    client::FrameClient,
+    error::{bail, ensure, FrameError, Result},
+    pending::PendingTx,
+};
+use ethers::{
+    providers::JsonRpcClient,
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, Eip1559TransactionRequest,
+        NameOrAddress, H256, U256,
+    },
+};
+use serde::Serialize;
+use serde_json::json;
+
+/// The size of a blob in bytes: 4096 field elements of 32 bytes.
+pub const BYTES_PER_BLOB: usize = 131_072;
+
+/// The version byte of a versioned hash derived from a KZG commitment.
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// An EIP-4844 (type 3) transaction carrying blobs, sent with
+/// [`FrameClient::send_blob_transaction`].
+///
+/// The blobs are identified by their `blob_versioned_hashes`, computed from the KZG commitments
+/// by the caller. `blobs` holds the encoded blob data for wallets that build the sidecar
+/// themselves; it may be left empty if Frame doesn't need it. Fields left as `None` are filled
+/// in by Frame, except `max_fee_per_blob_gas`, which is estimated by the client.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobTxRequest {
+    pub from: Address,
+    /// Blob transactions can't create contracts, so the recipient is required.
+    pub to: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// The maximum fee per blob gas, in Wei. `None` uses twice the current blob base fee.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blob_versioned_hashes: Vec<H256>,
+    /// The blobs, each exactly [`BYTES_PER_BLOB`] bytes of encoded field elements.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blobs: Vec<Bytes>,
+}
+
+impl BlobTxRequest {
+    /// Checks the blobs and versioned hashes for mistakes the node would reject less clearly.
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            !self.blob_versioned_hashes.is_empty() || !self.blobs.is_empty(),
+            "A blob transaction needs at least one blob or versioned hash"
+        );
+        ensure!(
+            self.blobs.is_empty()
+                || self.blob_versioned_hashes.is_empty()
+                || self.blobs.len() == self.blob_versioned_hashes.len(),
+            "Got {} blobs but {} versioned hashes",
+            self.blobs.len(),
+            self.blob_versioned_hashes.len()
+        );
+        for (index, blob) in self.blobs.iter().enumerate() {
+            ensure!(
+                blob.len() == BYTES_PER_BLOB,
+                "Blob {} is {} bytes, expected {}",
+                index,
+                blob.len(),
+                BYTES_PER_BLOB
+            );
+        }
+        for hash in &self.blob_versioned_hashes {
+            ensure!(
+                hash[0] == VERSIONED_HASH_VERSION_KZG,
+                "Versioned hash {:?} does not start with version byte {:#04x}",
+                hash,
+                VERSIONED_HASH_VERSION_KZG
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the EIP-1559 transaction passed to the before-send hook in place of this one.
+    /// The blobs, versioned hashes and blob fee have no counterpart in it.
+    fn to_typed(&self) -> TypedTransaction {
+        let mut tx = Eip1559TransactionRequest::new().from(self.from).to(self.to);
+        tx.value = self.value;
+        tx.data = self.data.clone();
+        tx.gas = self.gas;
+        tx.nonce = self.nonce;
+        tx.max_fee_per_gas = self.max_fee_per_gas;
+        tx.max_priority_fee_per_gas = self.max_priority_fee_per_gas;
+        tx.into()
+    }
+
+    /// Takes over the fields of a transaction the before-send hook returned in place of
+    /// [`BlobTxRequest::to_typed`]. A legacy gas price is used as both fee caps.
+    fn apply(&mut self, tx: &TypedTransaction) -> Result<()> {
+        let Some(NameOrAddress::Address(to)) = tx.to() else {
+            bail!("A blob transaction needs a recipient address");
+        };
+        self.to = *to;
+        if let Some(from) = tx.from() {
+            self.from = *from;
+        }
+        self.value = tx.value().copied();
+        self.data = tx.data().cloned();
+        self.gas = tx.gas().copied();
+        self.nonce = tx.nonce().copied();
+        match tx {
+            TypedTransaction::Eip1559(tx) => {
+                self.max_fee_per_gas = tx.max_fee_per_gas;
+                self.max_priority_fee_per_gas = tx.max_priority_fee_per_gas;
+            }
+            tx => {
+                self.max_fee_per_gas = tx.gas_price();
+                self.max_priority_fee_per_gas = tx.gas_price();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Sends an EIP-4844 (type 3) blob transaction through Frame and waits for its receipt.
+    ///
+    /// ethers can't encode type 3 transactions, so the request is passed to Frame as JSON and
+    /// the receipt is polled for by hash. Without `max_fee_per_blob_gas`, the fee cap is twice
+    /// the blob base fee from [`FrameClient::get_blob_base_fee`], leaving room for it to double
+    /// before inclusion. Apart from that, the transaction is sent like any other, with an EIP-1559
+    /// transaction without the blob fields standing in for it: a before-send hook sees that
+    /// transaction, and the fields of a transaction it returns instead are taken over, the
+    /// policy is enforced on it, and with simulation before sending enabled, it is simulated.
+    /// After any other open prompt in Frame is answered, Frame must still be connected to the
+    /// expected chain. With nonce management enabled, a transaction without a nonce gets the
+    /// next one of its sender, which is released again if the transaction never reaches Frame
+    /// or Frame refuses it. The client's receipt timeout covers both the prompt in Frame and
+    /// the wait for the receipt.
+    ///
+    /// # Parameters
+    /// - `tx`: The `BlobTxRequest` to send.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, H256, U256};
+    /// use frame_rs::{blob::BlobTxRequest, client::FrameClient};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///     let inbox: Address = "0x...".parse()?;
+    ///     let versioned_hash: H256 = "0x01...".parse()?;
+    ///
+    ///     let tx = BlobTxRequest {
+    ///         from,
+    ///         to: inbox,
+    ///         blob_versioned_hashes: vec![versioned_hash],
+    ///         ..Default::default()
+    ///     };
+    ///     let tx_hash = client.send_blob_transaction(tx).await?;
+    ///     println!("Transaction hash: {:?}", tx_hash);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the blobs or versioned hashes are malformed,
+    /// `FrameError::SendRejected` if the before-send hook rejects the transaction,
+    /// `FrameError::PolicyViolation` if the transaction breaks the client's policy,
+    /// `FrameError::ExecutionReverted` if the simulation before sending finds it would revert,
+    /// `FrameError::ChainMismatch` if Frame isn't connected to the expected chain, and
+    /// `FrameError::Unsupported` if the chain has no blob base fee or Frame or the node rejects
+    /// type 3 transactions. Returns an error if the transaction fails to be sent or if no
+    /// receipt is returned for it. If the user declines the transaction in Frame, the error is
+    /// `FrameError::UserRejected`.
+    pub async fn send_blob_transaction(&self, tx: BlobTxRequest) -> Result<H256> {
+        let receipt = self
+            .confirm_sent(self.send_blob_pending(tx), 1, None)
+            .await?;
+        Ok(receipt.transaction_hash)
+    }
+
+    /// Sends a blob transaction through Frame, returning once it has been submitted.
+    async fn send_blob_pending(&self, mut tx: BlobTxRequest) -> Result<PendingTx<'_, P>> {
+        tx.validate()?;
+        let mut typed = tx.to_typed();
+        let slot = self.prepare_send(&mut typed).await?;
+
+        let filled = async {
+            tx.apply(&typed)?;
+            if tx.max_fee_per_blob_gas.is_none() {
+                tx.max_fee_per_blob_gas = Some(self.get_blob_base_fee().await? * 2);
+            }
+            let mut params = serde_json::to_value(&tx).map_err(anyhow::Error::from)?;
+            params["type"] = json!("0x3");
+            Ok(params)
+        };
+        let sent = match filled.await {
+            Ok(params) => self
+                .send_rpc("eth_sendTransaction", json!([params]))
+                .await
+                .map_err(|err| (err, false)),
+            Err(err) => Err((err, true)),
+        };
+        let tx_hash = slot.settle(sent).await.map_err(unsupported_blob_error)?;
+        Ok(PendingTx::new(tx_hash, self))
+    }
+}
+
+/// Turns a rejection of the type 3 transaction itself into `FrameError::Unsupported`, keeping
+/// the node's message. Only errors that name the transaction type or report the method as
+/// missing are taken as such; other invalid parameters are returned as they are.
+fn unsupported_blob_error(err: FrameError) -> FrameError {
+    let FrameError::RpcError { message, .. } = &err else {
+        return err;
+    };
+    if err.is_method_not_found() || message.to_lowercase().contains("transaction type") {
+        FrameError::Unsupported(format!("EIP-4844 blob transactions ({})", message))
+    } else {
+        err
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{client::push_receipt, nonce::NonceCache, transport::FrameTransport};
+    use ethers::types::TransactionReceipt;
+    use std::time::Duration;
+
+    fn versioned_hash() -> H256 {
+        let mut hash = H256::random();
+        hash.0[0] = VERSIONED_HASH_VERSION_KZG;
+        hash
+    }
+
+    #[test]
+    fn test_validate_blob_tx() {
+        let tx = BlobTxRequest {
+            blob_versioned_hashes: vec![versioned_hash()],
+            ..Default::default()
+        };
+        assert!(tx.validate().is_ok());
+        assert!(BlobTxRequest::default().validate().is_err());
+
+        let mut unversioned = tx.clone();
+        unversioned.blob_versioned_hashes[0].0[0] = 0;
+        assert!(unversioned.validate().is_err());
+
+        let mut short_blob = tx.clone();
+        short_blob.blobs = vec![Bytes::from(vec![0; 32])];
+        let err = short_blob.validate().unwrap_err();
+        assert_eq!(err.to_string(), "Blob 0 is 32 bytes, expected 131072");
+
+        let mut mismatched = tx;
+        mismatched.blobs = vec![Bytes::from(vec![0; BYTES_PER_BLOB]); 2];
+        assert!(mismatched.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_blob_transaction() {
+        let tx_hash = H256::random();
+        let (client, mock) = FrameClient::mocked_with_rpc(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "result": tx_hash }),
+        ])
+        .await;
+        push_receipt(
+            &mock,
+            TransactionReceipt {
+                transaction_hash: tx_hash,
+                block_number: Some(1.into()),
+                ..Default::default()
+            },
+        );
+        mock.push(U256::from(7)).unwrap();
+
+        let tx = BlobTxRequest {
+            from: Address::random(),
+            to: Address::random(),
+            blob_versioned_hashes: vec![versioned_hash()],
+            ..Default::default()
+        };
+        assert_eq!(client.send_blob_transaction(tx).await.unwrap(), tx_hash);
+        mock.assert_request("eth_blobBaseFee", ()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_blob_send_checks_chain_after_open_prompts() {
+        let tx_hash = H256::random();
+        let (client, mock) = FrameClient::mocked_with_rpc(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "result": tx_hash }),
+        ])
+        .await;
+        *client.expected_chain_id.write().await = Some(U256::one());
+        push_receipt(
+            &mock,
+            TransactionReceipt {
+                transaction_hash: tx_hash,
+                block_number: Some(1.into()),
+                ..Default::default()
+            },
+        );
+        mock.push(U256::one()).unwrap();
+
+        let prompt = client.lock_prompt().await.unwrap();
+        let tx = BlobTxRequest {
+            max_fee_per_blob_gas: Some(U256::from(10)),
+            blob_versioned_hashes: vec![versioned_hash()],
+            ..Default::default()
+        };
+        let sender = client.clone();
+        let sending = tokio::spawn(async move { sender.send_blob_transaction(tx).await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        // The chain isn't checked while another prompt is open.
+        assert!(mock.assert_request("eth_chainId", ()).is_err());
+
+        drop(prompt);
+        assert_eq!(sending.await.unwrap().unwrap(), tx_hash);
+        mock.assert_request("eth_chainId", ()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_blob_send_timeout_keeps_nonce() {
+        // Frame never answers, as if the prompt were left open.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                sockets.push(socket);
+            }
+        });
+        let (mut client, mock) = FrameClient::mocked();
+        client.rpc = FrameTransport::new(url.parse().unwrap(), Default::default());
+        client.nonces = Some(NonceCache::default());
+        client.receipt_timeout = Some(Duration::from_millis(100));
+        mock.push(U256::from(3)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+
+        let from = Address::random();
+        let tx = BlobTxRequest {
+            from,
+            max_fee_per_blob_gas: Some(U256::from(10)),
+            blob_versioned_hashes: vec![versioned_hash()],
+            ..Default::default()
+        };
+        let err = client.send_blob_transaction(tx).await.unwrap_err();
+        assert!(matches!(err, FrameError::Timeout { tx_hash: None }));
+
+        mock.push(U256::from(1)).unwrap();
+        let nonces = client.nonces.as_ref().unwrap();
+        let (_, nonce) = client.reserve_nonce(nonces, from).await.unwrap();
+        assert_eq!(nonce, U256::from(4));
+    }
+
+    #[tokio::test]
+    async fn test_blob_transaction_rejected() {
+        let (client, _) = FrameClient::mocked_with_rpc(vec![json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": -32000, "message": "transaction type not supported" }
+        })])
+        .await;
+
+        let tx = BlobTxRequest {
+            max_fee_per_blob_gas: Some(U256::from(10)),
+            blob_versioned_hashes: vec![versioned_hash()],
+            ..Default::default()
+        };
+        let err = client.send_blob_transaction(tx).await.unwrap_err();
+        assert!(
+            matches!(&err, FrameError::Unsupported(feature)
+                if feature == "EIP-4844 blob transactions (transaction type not supported)"),
+            "{:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_blob_transaction_invalid_params() {
+        let (client, _) = FrameClient::mocked_with_rpc(vec![json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": -32602, "message": "max fee per blob gas too low" }
+        })])
+        .await;
+
+        let tx = BlobTxRequest {
+            max_fee_per_blob_gas: Some(U256::from(10)),
+            blob_versioned_hashes: vec![versioned_hash()],
+            ..Default::default()
+        };
+        let err = client.send_blob_transaction(tx).await.unwrap_err();
+        assert!(
+            matches!(&err, FrameError::RpcError { code: -32602, message }
+                if message == "max fee per blob gas too low"),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_blob_tx_hook_view() {
+        let tx = BlobTxRequest {
+            from: Address::random(),
+            to: Address::random(),
+            value: Some(U256::from(1)),
+            max_fee_per_gas: Some(U256::from(20)),
+            max_priority_fee_per_gas: Some(U256::from(2)),
+            blob_versioned_hashes: vec![versioned_hash()],
+            ..Default::default()
+        };
+        let mut typed = tx.to_typed();
+        let mut applied = tx.clone();
+        applied.apply(&typed).unwrap();
+        assert_eq!(applied, tx);
+
+        typed.set_gas(50_000);
+        typed.set_nonce(7);
+        applied.apply(&typed).unwrap();
+        assert_eq!(applied.gas, Some(U256::from(50_000)));
+        assert_eq!(applied.nonce, Some(U256::from(7)));
+        assert_eq!(applied.blob_versioned_hashes, tx.blob_versioned_hashes);
+
+        let deployment = TypedTransaction::Eip1559(Eip1559TransactionRequest::new());
+        assert!(applied.apply(&deployment).is_err());
+    }
+
+    #[test]
+    fn test_blob_tx_params() {
+        let hash = versioned_hash();
+        let tx = BlobTxRequest {
+            max_fee_per_blob_gas: Some(U256::from(10)),
+            blob_versioned_hashes: vec![hash],
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&tx).unwrap(),
+            json!({
+                "from": Address::zero(),
+                "to": Address::zero(),
+                "maxFeePerBlobGas": "0xa",
+                "blobVersionedHashes": [hash],
+            })
+        );
+    }
+}
@@ -0,0 +1,478 @@
+use crate::{
+    approval::{BeforeSend, BeforeSendHook},
+    client::{FrameClient, DEFAULT_HOST, DEFAULT_PORT},
+    error::{ensure, FrameError, Result},
+    middleware::{FrameMiddleware, MiddlewareStack},
+    multicall::MULTICALL3_ADDRESS,
+    nonce::NonceCache,
+    pending::DEFAULT_DROPPED_AFTER_POLLS,
+    policy::TxPolicy,
+    transport::FrameTransport,
+};
+use anyhow::Context;
+use ethers::{
+    providers::{JsonRpcClient, Provider, Ws},
+    types::{Address, U256},
+};
+use reqwest::{Client, Url};
+use std::{net::IpAddr, sync::Arc, time::Duration};
+use tokio::sync::RwLock;
+
+/// The default delay between retries of a failed request.
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// A builder for configuring and connecting a [`FrameClient`].
+///
+/// The builder starts from the same defaults as [`FrameClient::new`]: host `127.0.0.1`,
+/// port `1248`, an automatic network switch when a chain ID is set, no timeouts and no retries.
+///
+/// # Examples
+/// ```no_run
+/// use ethers::types::U256;
+/// use frame_rs::client::FrameClient;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let client = FrameClient::builder()
+///         .chain_id(U256::from(1))
+///         .port(9000)
+///         .request_timeout(Duration::from_secs(30))
+///         .max_retries(3)
+///         .build()
+///         .await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FrameClientBuilder {
+    host: String,
+    port: u16,
+    chain_id: Option<U256>,
+    skip_network_switch: bool,
+    connect_timeout: Option<Duration>,
+    request_timeout: Option<Duration>,
+    max_retries: usize,
+    retry_delay: Duration,
+    allow_unsafe_signing: bool,
+    receipt_timeout: Option<Duration>,
+    manage_nonces: bool,
+    multicall_address: Address,
+    auto_access_list: bool,
+    simulate_before_send: bool,
+    before_send: BeforeSend,
+    policy: TxPolicy,
+    allow_chain_drift: bool,
+    auto_resubmit: bool,
+    dropped_after_polls: usize,
+    middlewares: MiddlewareStack,
+}
+
+impl Default for FrameClientBuilder {
+    fn default() -> Self {
+        Self {
+            host: DEFAULT_HOST.to_string(),
+            port: DEFAULT_PORT,
+            chain_id: None,
+            skip_network_switch: false,
+            connect_timeout: None,
+            request_timeout: None,
+            max_retries: 0,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            allow_unsafe_signing: false,
+            receipt_timeout: None,
+            manage_nonces: false,
+            multicall_address: MULTICALL3_ADDRESS,
+            auto_access_list: false,
+            simulate_before_send: false,
+            before_send: BeforeSend::default(),
+            policy: TxPolicy::default(),
+            allow_chain_drift: false,
+            auto_resubmit: false,
+            dropped_after_polls: DEFAULT_DROPPED_AFTER_POLLS,
+            middlewares: MiddlewareStack::default(),
+        }
+    }
+}
+
+impl FrameClientBuilder {
+    /// Creates a new builder with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the host of the Frame wallet's RPC endpoint. Defaults to `127.0.0.1`.
+    pub fn host(mut self, host: &str) -> Self {
+        self.host = host.to_string();
+        self
+    }
+
+    /// Sets the port of the Frame wallet's RPC endpoint. Defaults to `1248`.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Sets the chain ID the client should switch Frame to when it is built.
+    ///
+    /// If no chain ID is set, Frame is left on whichever network it currently has selected.
+    pub fn chain_id(mut self, chain_id: U256) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Skips the `wallet_switchEthereumChain` request on build, even if a chain ID is set.
+    pub fn skip_network_switch(mut self, skip: bool) -> Self {
+        self.skip_network_switch = skip;
+        self
+    }
+
+    /// Sets the timeout for establishing a connection to Frame. Defaults to no timeout.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for each HTTP request to Frame, from sending the request until the
+    /// response body is read. Defaults to no timeout.
+    ///
+    /// This covers wallet requests such as [`FrameClient::switch_network`] as well as the
+    /// provider's requests of a client built with [`FrameClientBuilder::build`]. A request that
+    /// exceeds it fails with `FrameError::Timeout`, and so does a signing or sending request
+    /// that waits longer than this for another prompt in Frame to be answered, so a forgotten
+    /// prompt doesn't block the application.
+    ///
+    /// The timeout only applies to HTTP requests. The provider of a client built with
+    /// [`FrameClientBuilder::build_ws`] sends its requests, including transactions, over the
+    /// WebSocket without a timeout; bound those with
+    /// [`FrameClientBuilder::receipt_timeout`] or `tokio::time::timeout`.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how many times a request is retried when it can't connect to Frame, for example
+    /// because Frame is restarting. Defaults to `0`.
+    ///
+    /// Timed-out requests are not retried, since a signing or sending request may already be
+    /// waiting in Frame and retrying it would prompt the user twice.
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay between retries of a failed request. Defaults to 500 milliseconds.
+    pub fn retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Allows [`FrameClient::sign_hash_unsafe`] to sign raw hashes with `eth_sign`. Defaults to
+    /// `false`.
+    ///
+    /// Only enable this for legacy verifiers that require it: a raw hash can't be inspected, so
+    /// it may just as well be the hash of a transaction or permit that drains the account.
+    pub fn allow_unsafe_signing(mut self, allow: bool) -> Self {
+        self.allow_unsafe_signing = allow;
+        self
+    }
+
+    /// Sets how long sending a transaction may take, from the prompt in Frame until the receipt,
+    /// before `FrameError::Timeout` is returned. Defaults to waiting indefinitely.
+    ///
+    /// This applies to every method that waits for a receipt and isn't given its own timeout,
+    /// such as [`FrameClient::send_gas_token`].
+    pub fn receipt_timeout(mut self, timeout: Duration) -> Self {
+        self.receipt_timeout = Some(timeout);
+        self
+    }
+
+    /// Assigns nonces to transactions locally instead of leaving them to Frame. Defaults to
+    /// `false`.
+    ///
+    /// The client then tracks the next nonce of each account on each chain, starting from the
+    /// pending transaction count, so transactions sent in quick succession, also from clones of
+    /// the client, don't collide. Call [`FrameClient::sync_nonces`] after sending transactions
+    /// from the Frame UI directly.
+    pub fn manage_nonces(mut self, enabled: bool) -> Self {
+        self.manage_nonces = enabled;
+        self
+    }
+
+    /// Sets the address of the Multicall3 contract used by [`FrameClient::multicall`], for
+    /// chains that deploy it elsewhere. Defaults to [`MULTICALL3_ADDRESS`].
+    pub fn multicall_address(mut self, address: Address) -> Self {
+        self.multicall_address = address;
+        self
+    }
+
+    /// Attaches an EIP-2930 access list, generated with `eth_createAccessList`, to every contract
+    /// call sent through the client that doesn't have one. Defaults to `false`.
+    ///
+    /// Legacy transactions are sent as EIP-2930 (type 1) transactions to carry the list. On
+    /// nodes that don't implement `eth_createAccessList`, transactions are sent unchanged.
+    pub fn auto_access_list(mut self, enabled: bool) -> Self {
+        self.auto_access_list = enabled;
+        self
+    }
+
+    /// Simulates every transaction with [`FrameClient::simulate`] before it is sent, and aborts
+    /// with `FrameError::ExecutionReverted`, carrying the decoded revert reason, if it would
+    /// revert. The user is then never prompted in Frame. Blob transactions are simulated without
+    /// their blobs, as the EIP-1559 transaction a before-send hook sees. Defaults to `false`.
+    pub fn simulate_before_send(mut self, enabled: bool) -> Self {
+        self.simulate_before_send = enabled;
+        self
+    }
+
+    /// Sets a hook that every send method calls with the transaction before it reaches Frame,
+    /// for example to show an own confirmation. The hook can approve the transaction, replace
+    /// it with a modified one, or reject it, which aborts the send with
+    /// `FrameError::SendRejected`. It runs before the nonce is reserved, so rejected
+    /// transactions don't use up nonces. Blob transactions are passed to the hook as EIP-1559
+    /// transactions without their blob fields.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use frame_rs::{approval::Approval, client::FrameClient};
+    /// use futures::FutureExt;
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::builder()
+    ///         .on_before_send(Arc::new(|tx| {
+    ///             let approval = match tx.to() {
+    ///                 Some(_) => Approval::Approve,
+    ///                 None => Approval::Reject("contract deployments are disabled".to_string()),
+    ///             };
+    ///             async move { Ok(approval) }.boxed()
+    ///         }))
+    ///         .build()
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn on_before_send(mut self, hook: BeforeSendHook) -> Self {
+        self.before_send = BeforeSend::new(hook);
+        self
+    }
+
+    /// Sends transactions on whatever chain Frame is connected to, instead of failing with
+    /// `FrameError::ChainMismatch` when it isn't the expected chain, see
+    /// [`FrameClient::expected_chain_id`]. Defaults to `false`.
+    pub fn allow_chain_drift(mut self, allow: bool) -> Self {
+        self.allow_chain_drift = allow;
+        self
+    }
+
+    /// Sets how many polls in a row must find no transaction, after the node has seen it, before
+    /// a pending transaction is considered dropped from the mempool. Defaults to
+    /// [`DEFAULT_DROPPED_AFTER_POLLS`].
+    pub fn dropped_after_polls(mut self, polls: usize) -> Self {
+        self.dropped_after_polls = polls;
+        self
+    }
+
+    /// Sends a transaction that was dropped from the mempool through Frame again, with the same
+    /// nonce and fees, instead of failing with `FrameError::TransactionDropped`. Each
+    /// resubmission opens a new prompt in Frame. A transaction is resubmitted at most
+    /// [`MAX_RESUBMISSIONS`](crate::pending::MAX_RESUBMISSIONS) times. Defaults to `false`.
+    pub fn auto_resubmit(mut self, enabled: bool) -> Self {
+        self.auto_resubmit = enabled;
+        self
+    }
+
+    /// Sets the [`TxPolicy`] enforced on every transaction before it is sent to Frame or signed
+    /// by it. Defaults to a policy that allows everything. The policy can be replaced later with
+    /// [`FrameClient::set_policy`].
+    pub fn policy(mut self, policy: TxPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Adds a [`FrameMiddleware`] that is called around every request sent to Frame over HTTP.
+    ///
+    /// Middlewares compose in the order they are added: the first one added sees each request
+    /// first and its response last.
+    pub fn with_middleware(mut self, middleware: impl FrameMiddleware + 'static) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Builds the `FrameClient`, switching Frame to the configured chain ID unless the switch
+    /// is skipped or no chain ID was set.
+    ///
+    /// # Errors
+    /// Returns an error if the port is zero, the host is not a valid IP address or hostname,
+    /// or if the network switch request fails.
+    pub async fn build(self) -> Result<FrameClient> {
+        let transport = self.transport()?;
+        self.connect(Provider::new(transport.clone()), transport)
+            .await
+    }
+
+    /// Builds a WebSocket-backed `FrameClient` connected to Frame's `ws://` endpoint on the
+    /// configured host and port. Wallet requests are still sent over HTTP.
+    ///
+    /// The request timeout only applies to the wallet requests sent over HTTP, not to the
+    /// provider's requests over the WebSocket.
+    ///
+    /// # Errors
+    /// Returns an error if the port is zero, the host is not a valid IP address or hostname,
+    /// if the WebSocket connection cannot be established or if the network switch request fails.
+    pub async fn build_ws(self) -> Result<FrameClient<Ws>> {
+        let transport = self.transport()?;
+        let ws = Ws::connect(self.url("ws"))
+            .await
+            .map_err(|err| FrameError::ConnectionFailed(err.to_string()))?;
+        self.connect(Provider::new(ws), transport).await
+    }
+
+    fn url(&self, scheme: &str) -> String {
+        match self.host.parse::<IpAddr>() {
+            Ok(IpAddr::V6(_)) => format!("{}://[{}]:{}", scheme, self.host, self.port),
+            _ => format!("{}://{}:{}", scheme, self.host, self.port),
+        }
+    }
+
+    fn transport(&self) -> Result<FrameTransport> {
+        ensure!(self.port != 0, "Invalid Frame RPC port: 0");
+        ensure!(
+            is_valid_host(&self.host),
+            "Invalid Frame RPC host: {:?}",
+            self.host
+        );
+
+        let mut client = Client::builder();
+        if let Some(timeout) = self.connect_timeout {
+            client = client.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.request_timeout {
+            client = client.timeout(timeout);
+        }
+
+        let url = Url::parse(&self.url("http")).context("Invalid Frame RPC URL")?;
+        Ok(FrameTransport::new(url, client.build()?)
+            .with_retries(self.max_retries, self.retry_delay)
+            .with_middlewares(self.middlewares.clone()))
+    }
+
+    async fn connect<P: JsonRpcClient>(
+        self,
+        provider: Provider<P>,
+        rpc: FrameTransport,
+    ) -> Result<FrameClient<P>> {
+        let client = FrameClient {
+            provider: Arc::new(provider),
+            rpc_url: self.url("http"),
+            rpc,
+            prompt_queue: Default::default(),
+            allow_unsafe_signing: self.allow_unsafe_signing,
+            receipt_timeout: self.receipt_timeout,
+            nonces: self.manage_nonces.then(NonceCache::default),
+            multicall_address: self.multicall_address,
+            auto_access_list: self.auto_access_list,
+            request_timeout: self.request_timeout,
+            simulate_before_send: self.simulate_before_send,
+            before_send: self.before_send,
+            policy: Arc::new(RwLock::new(self.policy)),
+            expected_chain_id: Arc::new(RwLock::new(self.chain_id)),
+            allow_chain_drift: self.allow_chain_drift,
+            auto_resubmit: self.auto_resubmit,
+            dropped_after_polls: self.dropped_after_polls,
+            drop_stats: Default::default(),
+        };
+
+        if let Some(chain_id) = self.chain_id.filter(|_| !self.skip_network_switch) {
+            client.switch_network(chain_id).await?;
+        }
+
+        Ok(client)
+    }
+}
+
+/// Returns `true` if `host` is an IP address or a valid hostname.
+fn is_valid_host(host: &str) -> bool {
+    if host.parse::<IpAddr>().is_ok() {
+        return true;
+    }
+
+    !host.is_empty()
+        && host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_build_without_network_switch() {
+        let client = FrameClientBuilder::new()
+            .chain_id(U256::from(1))
+            .host("localhost")
+            .port(9000)
+            .skip_network_switch(true)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(client.rpc_url, "http://localhost:9000");
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        // A server that accepts connections but never responds.
+        let listener = tokio::net::TcpListener::bind((DEFAULT_HOST, 0))
+            .await
+            .unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.push(socket);
+            }
+        });
+        let client = FrameClientBuilder::new()
+            .port(port)
+            .request_timeout(Duration::from_millis(50))
+            .build()
+            .await
+            .unwrap();
+
+        let err = client.get_chain_id().await.unwrap_err();
+        assert!(matches!(err, FrameError::Timeout { tx_hash: None }));
+        assert_eq!(err.to_string(), "timed out");
+        let err = client.switch_network(U256::from(1)).await.unwrap_err();
+        assert!(matches!(err, FrameError::Timeout { tx_hash: None }));
+    }
+
+    #[tokio::test]
+    async fn test_build_validates_host_and_port() {
+        let builder = FrameClientBuilder::new().skip_network_switch(true);
+
+        assert!(builder.clone().port(0).build().await.is_err());
+        assert!(builder.clone().host("not a host").build().await.is_err());
+        assert!(builder.clone().host("-frame.local").build().await.is_err());
+
+        let client = builder.host("::1").build().await.unwrap();
+        assert_eq!(client.rpc_url, "http://[::1]:1248");
+    }
+
+    #[tokio::test]
+    async fn test_build_ws_without_frame() {
+        let builder = FrameClientBuilder::new().port(1).skip_network_switch(true);
+        assert_eq!(builder.url("ws"), "ws://127.0.0.1:1");
+
+        let err = builder.build_ws().await.err().unwrap();
+        assert!(matches!(err, FrameError::ConnectionFailed(_)));
+    }
+}
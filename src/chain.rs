@@ -0,0 +1,424 @@
+use crate::{
+    client::FrameClient,
+    error::{bail, decode_revert_reason, FrameError, Result},
+};
+use ethers::{
+    middleware::Middleware,
+    providers::{JsonRpcClient, Provider},
+    types::{
+        transaction::eip2718::TypedTransaction, Block, BlockId, BlockNumber, Transaction,
+        TransactionReceipt, H256,
+    },
+};
+use std::time::Duration;
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Retrieves a block by number, tag or hash, with the hashes of its transactions.
+    ///
+    /// # Parameters
+    /// - `id`: The `BlockId` of the block, e.g. `BlockNumber::Latest.into()` or a block hash.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the block, or `None` if the node does not
+    /// know it.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{BlockId, BlockNumber, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let id = BlockId::Number(BlockNumber::Number(19_000_000.into()));
+    ///
+    ///     if let Some(block) = client.get_block(id).await? {
+    ///         println!("Block has {} transactions", block.transactions.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the block cannot be fetched from the connected provider.
+    pub async fn get_block(&self, id: BlockId) -> Result<Option<Block<H256>>> {
+        let block = self.provider.get_block(id).await?;
+        Ok(block)
+    }
+
+    /// Retrieves a block by number, tag or hash, with its full transactions.
+    ///
+    /// This behaves like [`FrameClient::get_block`], but includes every `Transaction` of the
+    /// block instead of only its hash.
+    ///
+    /// # Errors
+    /// Returns an error if the block cannot be fetched from the connected provider.
+    pub async fn get_block_with_txs(&self, id: BlockId) -> Result<Option<Block<Transaction>>> {
+        let block = self.provider.get_block_with_txs(id).await?;
+        Ok(block)
+    }
+
+    /// Retrieves the latest block, with the hashes of its transactions.
+    ///
+    /// # Errors
+    /// Returns an error if the block cannot be fetched from the connected provider, or if the
+    /// provider returns no latest block.
+    pub async fn get_latest_block(&self) -> Result<Block<H256>> {
+        match self.get_block(BlockNumber::Latest.into()).await? {
+            Some(block) => Ok(block),
+            None => bail!("The provider returned no latest block"),
+        }
+    }
+
+    /// Retrieves a transaction by its hash.
+    ///
+    /// # Parameters
+    /// - `hash`: The `H256` hash of the transaction, e.g. as returned by
+    ///   [`FrameClient::send_gas_token`].
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the transaction, or `None` if the node does
+    /// not know it.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction cannot be fetched from the connected provider.
+    pub async fn get_transaction(&self, hash: H256) -> Result<Option<Transaction>> {
+        let tx = self.provider.get_transaction(hash).await?;
+        Ok(tx)
+    }
+
+    /// Retrieves the receipt of a transaction by its hash.
+    ///
+    /// # Parameters
+    /// - `hash`: The `H256` hash of the transaction.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the receipt, or `None` if the transaction is
+    /// unknown or has not been mined yet.
+    ///
+    /// # Errors
+    /// Returns an error if the receipt cannot be fetched from the connected provider.
+    pub async fn get_transaction_receipt(&self, hash: H256) -> Result<Option<TransactionReceipt>> {
+        let receipt = self.provider.get_transaction_receipt(hash).await?;
+        Ok(receipt)
+    }
+
+    /// Waits for a transaction to be mined and confirmed by a number of blocks.
+    ///
+    /// The receipt is polled at the provider's polling interval until it appears, then the
+    /// latest block number is polled until the block containing the transaction has
+    /// `confirmations` blocks on top of it, counting itself. Unlike the sending methods, this
+    /// works for any transaction hash, including transactions sent outside of Frame.
+    ///
+    /// The receipt is fetched again on every poll, so if the transaction leaves the canonical
+    /// chain during the wait, e.g. in a reorg, the wait starts over once it is mined again, and
+    /// the receipt that is returned is always the current one.
+    ///
+    /// # Parameters
+    /// - `hash`: The `H256` hash of the transaction.
+    /// - `confirmations`: The number of blocks required, counting the block the transaction was
+    ///   mined in. `0` and `1` both return as soon as the receipt appears.
+    /// - `timeout`: How long to wait before giving up.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `TransactionReceipt` of the transaction.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{H256, U256};
+    /// use frame_rs::client::FrameClient;
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let hash: H256 = "0x...".parse()?;
+    ///
+    ///     let receipt = client
+    ///         .wait_for_receipt(hash, 3, Duration::from_secs(120))
+    ///         .await?;
+    ///     println!("Mined in block {:?}", receipt.block_number);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `FrameError::Timeout` if the confirmations have not accumulated before `timeout`
+    /// expires, or an error if the receipt or block number cannot be fetched.
+    pub async fn wait_for_receipt(
+        &self,
+        hash: H256,
+        confirmations: usize,
+        timeout: Duration,
+    ) -> Result<TransactionReceipt> {
+        let interval = self.provider.get_interval();
+        self.wait_for_receipt_with_interval(hash, confirmations, timeout, interval)
+            .await
+    }
+
+    /// Waits for a transaction to be mined and confirmed by a number of blocks, polling every
+    /// `interval`.
+    ///
+    /// This behaves like [`FrameClient::wait_for_receipt`], which polls at the provider's
+    /// polling interval.
+    ///
+    /// # Errors
+    /// Returns `FrameError::Timeout` if the confirmations have not accumulated before `timeout`
+    /// expires, or an error if the receipt or block number cannot be fetched.
+    pub async fn wait_for_receipt_with_interval(
+        &self,
+        hash: H256,
+        confirmations: usize,
+        timeout: Duration,
+        interval: Duration,
+    ) -> Result<TransactionReceipt> {
+        tokio::time::timeout(timeout, self.poll_receipt(hash, confirmations, interval))
+            .await
+            .map_err(|_| FrameError::Timeout {
+                tx_hash: Some(hash),
+            })?
+    }
+
+    /// Recovers why a mined transaction reverted by replaying it with `eth_call` at the block
+    /// it was mined in.
+    ///
+    /// `Error(string)` and `Panic(uint256)` revert data are decoded; other revert data, such as
+    /// a custom error, is returned as hex. The replay runs against the state at the end of the
+    /// block, which may differ from the state the transaction saw; use
+    /// [`FrameClient::get_revert_reason_at`] to replay it at another block, e.g. the parent.
+    ///
+    /// # Parameters
+    /// - `tx_hash`: The `H256` hash of the reverted transaction.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the revert reason.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::{client::FrameClient, error::FrameError};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     if let Err(FrameError::TransactionReverted { hash, .. }) =
+    ///         client.send_gas_token(from, to, U256::exp10(18)).await
+    ///     {
+    ///         println!("Reverted: {}", client.get_revert_reason(hash).await?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the transaction is unknown or not mined, if the replay does not
+    /// revert, or if the replay fails for another reason.
+    pub async fn get_revert_reason(&self, tx_hash: H256) -> Result<String> {
+        replay_revert_reason(&self.provider, tx_hash, None).await
+    }
+
+    /// Recovers why a transaction reverted by replaying it with `eth_call` at `block`.
+    ///
+    /// This behaves like [`FrameClient::get_revert_reason`], which replays the transaction at
+    /// the block it was mined in.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction is unknown, if the replay does not revert, or if the
+    /// replay fails for another reason.
+    pub async fn get_revert_reason_at(&self, tx_hash: H256, block: BlockId) -> Result<String> {
+        replay_revert_reason(&self.provider, tx_hash, Some(block)).await
+    }
+
+    async fn poll_receipt(
+        &self,
+        hash: H256,
+        confirmations: usize,
+        interval: Duration,
+    ) -> Result<TransactionReceipt> {
+        loop {
+            if let Some(receipt) = confirmed_receipt(&self.provider, hash, confirmations).await? {
+                return Ok(receipt);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+/// Fetches the receipt of `hash` and returns it once the block the transaction was mined in
+/// has `confirmations` blocks on top of it, counting itself. `0` and `1` both return any
+/// receipt.
+pub(crate) async fn confirmed_receipt<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    hash: H256,
+    confirmations: usize,
+) -> Result<Option<TransactionReceipt>> {
+    let Some(receipt) = provider.get_transaction_receipt(hash).await? else {
+        return Ok(None);
+    };
+    let Some(mined_in) = receipt.block_number else {
+        return Ok(None);
+    };
+    if confirmations > 1 {
+        let latest = provider.get_block_number().await?;
+        if latest.as_u64() + 1 < mined_in.as_u64() + confirmations as u64 {
+            return Ok(None);
+        }
+    }
+    Ok(Some(receipt))
+}
+
+/// Replays a transaction at `block`, or at the block it was mined in, and returns its revert
+/// reason.
+pub(crate) async fn replay_revert_reason<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    tx_hash: H256,
+    block: Option<BlockId>,
+) -> Result<String> {
+    let Some(tx) = provider.get_transaction(tx_hash).await? else {
+        bail!("Tx {:?} not found", tx_hash);
+    };
+    let Some(block) = block.or(tx.block_number.map(Into::into)) else {
+        bail!("Tx {:?} is not mined yet", tx_hash);
+    };
+
+    let call: TypedTransaction = (&tx).into();
+    match provider
+        .call(&call, Some(block))
+        .await
+        .map_err(FrameError::from_revert)
+    {
+        Ok(_) => bail!("Replaying tx {:?} did not revert", tx_hash),
+        Err(FrameError::ExecutionReverted {
+            data: Some(data), ..
+        }) if !data.is_empty() && decode_revert_reason(&data).is_none() => Ok(data.to_string()),
+        Err(FrameError::ExecutionReverted { reason, .. }) => Ok(reason),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        providers::{JsonRpcError, MockResponse},
+        types::{Bytes, U64},
+    };
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_get_latest_block() {
+        let (client, mock) = FrameClient::mocked();
+        let block = Block::<H256> {
+            number: Some(U64::from(42)),
+            ..Default::default()
+        };
+        mock.push(block).unwrap();
+
+        let block = client.get_latest_block().await.unwrap();
+        assert_eq!(block.number, Some(U64::from(42)));
+        mock.assert_request("eth_getBlockByNumber", ("latest", false))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_receipt_confirmations() {
+        let (client, mock) = FrameClient::mocked();
+        let hash = H256::random();
+        let receipt = TransactionReceipt {
+            transaction_hash: hash,
+            block_number: Some(U64::from(10)),
+            ..Default::default()
+        };
+        mock.push(U64::from(11)).unwrap();
+        mock.push(receipt.clone()).unwrap();
+        mock.push(U64::from(10)).unwrap();
+        mock.push(receipt.clone()).unwrap();
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+
+        let mined = client
+            .wait_for_receipt(hash, 2, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(mined, receipt);
+        mock.assert_request("eth_getTransactionReceipt", [hash])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_receipt_timeout() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+
+        let hash = H256::random();
+        let err = client
+            .wait_for_receipt(hash, 1, Duration::from_millis(5))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FrameError::Timeout { tx_hash: Some(h) } if h == hash));
+    }
+
+    #[tokio::test]
+    async fn test_get_revert_reason() {
+        let (client, mock) = FrameClient::mocked();
+        let hash = H256::random();
+        let tx = Transaction {
+            hash,
+            block_number: Some(U64::from(7)),
+            ..Default::default()
+        };
+        let revert = |data: Bytes| {
+            MockResponse::Error(JsonRpcError {
+                code: 3,
+                message: "execution reverted".to_string(),
+                data: Some(json!(data)),
+            })
+        };
+        let custom_error = Bytes::from(ethers::utils::id("Unauthorized()").to_vec());
+        let mut reason = ethers::utils::id("Error(string)").to_vec();
+        reason.extend(ethers::abi::encode(&[ethers::abi::Token::String(
+            "Not owner".to_string(),
+        )]));
+        mock.push_response(revert(custom_error.clone()));
+        mock.push(tx.clone()).unwrap();
+        mock.push_response(revert(reason.into()));
+        mock.push(tx).unwrap();
+
+        assert_eq!(client.get_revert_reason(hash).await.unwrap(), "Not owner");
+        mock.assert_request("eth_getTransactionByHash", [hash])
+            .unwrap();
+        let call: TypedTransaction = (&Transaction::default()).into();
+        mock.assert_request("eth_call", (&call, "0x7")).unwrap();
+
+        let parent = BlockId::Number(BlockNumber::Number(U64::from(6)));
+        assert_eq!(
+            client.get_revert_reason_at(hash, parent).await.unwrap(),
+            custom_error.to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_receipt_restarts_after_reorg() {
+        let (client, mock) = FrameClient::mocked();
+        let hash = H256::random();
+        let receipt_in = |block: u64| TransactionReceipt {
+            transaction_hash: hash,
+            block_number: Some(U64::from(block)),
+            ..Default::default()
+        };
+        // Mined in block 10, reorged out, then mined again in block 11.
+        mock.push(U64::from(12)).unwrap();
+        mock.push(receipt_in(11)).unwrap();
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+        mock.push(U64::from(10)).unwrap();
+        mock.push(receipt_in(10)).unwrap();
+
+        let receipt = client
+            .wait_for_receipt_with_interval(hash, 2, Duration::from_secs(5), Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(receipt, receipt_in(11));
+    }
+}
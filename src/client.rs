@@ -1,17 +1,75 @@
-use anyhow::{bail, Result};
+use crate::{
+    approval::BeforeSend,
+    builder::FrameClientBuilder,
+    eip712::ToTypedData,
+    error::{bail, FrameError, Result, UNRECOGNIZED_CHAIN_CODE},
+    nonce::NonceCache,
+    pending::{DropCounters, PendingTx},
+    policy::TxPolicy,
+    sig::SignatureExt,
+    transport::FrameTransport,
+    types::{
+        AssetType, ChainConfig, FeeOverrides, GasStrategy, Permission, PermissionRequest,
+        RejectionPolicy, SendOptions, WatchAssetOptions,
+    },
+};
 use ethers::{
     middleware::Middleware,
-    providers::{Http, Provider},
-    types::{Address, TransactionRequest, H256, U256},
+    providers::{Http, HttpClientError, JsonRpcClient, Provider, Ws},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, BlockId, BlockNumber, Bytes, Signature,
+        TransactionReceipt, TransactionRequest, H256, U256,
+    },
+    utils::keccak256,
+};
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::{convert::TryFrom, future::Future, sync::Arc, time::Duration};
+use tokio::{
+    sync::{Mutex, MutexGuard, RwLock},
+    time::Instant,
 };
-use reqwest::Client;
-use serde_json::json;
-use std::{convert::TryFrom, sync::Arc};
 
+/// The default host of the Frame wallet's RPC endpoint.
+pub const DEFAULT_HOST: &str = "127.0.0.1";
+
+/// The default port of the Frame wallet's RPC endpoint.
+pub const DEFAULT_PORT: u16 = 1248;
+
+/// How long [`FrameClient::ping`] waits for Frame to respond.
+pub const PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A client for the Frame wallet, generic over the transport used by its provider.
+///
+/// `FrameClient` uses HTTP through a [`FrameTransport`] by default. A WebSocket provider, which is required for
+/// subscriptions, can be created with [`FrameClient::new_ws`]. Wallet requests such as
+/// `wallet_switchEthereumChain` are always sent over HTTP to `rpc_url`, since Frame does not
+/// accept them over WebSocket.
+///
+/// Requests that open a prompt in Frame, such as signing or sending a transaction, are queued
+/// and sent one at a time, so that concurrent calls (including from clones of the client) don't
+/// open several Frame windows at once.
 #[derive(Clone)]
-pub struct FrameClient {
-    pub provider: Arc<Provider<Http>>,
-    rpc_url: String,
+pub struct FrameClient<P = FrameTransport> {
+    pub provider: Arc<Provider<P>>,
+    pub(crate) rpc_url: String,
+    pub(crate) rpc: FrameTransport,
+    pub(crate) prompt_queue: Arc<Mutex<()>>,
+    pub(crate) allow_unsafe_signing: bool,
+    pub(crate) receipt_timeout: Option<Duration>,
+    pub(crate) nonces: Option<NonceCache>,
+    pub(crate) multicall_address: Address,
+    pub(crate) auto_access_list: bool,
+    pub(crate) request_timeout: Option<Duration>,
+    pub(crate) simulate_before_send: bool,
+    pub(crate) before_send: BeforeSend,
+    pub(crate) policy: Arc<RwLock<TxPolicy>>,
+    pub(crate) expected_chain_id: Arc<RwLock<Option<U256>>>,
+    pub(crate) allow_chain_drift: bool,
+    pub(crate) auto_resubmit: bool,
+    pub(crate) dropped_after_polls: usize,
+    pub(crate) drop_stats: Arc<DropCounters>,
 }
 
 impl FrameClient {
@@ -22,10 +80,12 @@ impl FrameClient {
     ///
     /// # Parameters
     /// - `chain_id`: The chain ID of the Ethereum network you want to connect to. This must be provided
-    /// as a `U256` value that corresponds to the desired network. For example, to connect to the Ethereum
-    /// Mainnet, you would pass `U256::from(1)`.
+    ///   as a `U256` value that corresponds to the desired network. For example, to connect to the Ethereum
+    ///   Mainnet, you would pass `U256::from(1)`.
     /// - `host`: The host address of the Frame wallet's RPC endpoint. This is optional and defaults to
-    /// 127.0.0.1. If the Frame wallet is running on a different host, you can specify it here.
+    ///   127.0.0.1. If the Frame wallet is running on a different host, you can specify it here.
+    /// - `port`: The port of the Frame wallet's RPC endpoint. This is optional and defaults to 1248.
+    ///   If Frame is running behind a proxy or on a non-default port, you can specify it here.
     ///
     /// # Returns
     /// Returns a `Result` wrapping a new `FrameClient` instance if the connection and network switch
@@ -40,7 +100,7 @@ impl FrameClient {
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let chain_id = U256::from(1); // Ethereum Mainnet
-    ///     let client = FrameClient::new(chain_id, Some("0.0.0.0")).await?;
+    ///     let client = FrameClient::new(chain_id, Some("0.0.0.0"), Some(1248)).await?;
     ///
     ///     Ok(())
     /// }
@@ -50,17 +110,299 @@ impl FrameClient {
     /// This method will return an error if the connection to the Frame wallet cannot be established,
     /// or if the network switch request fails. The error will contain details about the failure.
     /// It will not return an error for an invalid chain ID, as the chain ID is not validated here.
-    pub async fn new(chain_id: U256, host: Option<&str>) -> Result<Self> {
-        let host = host.unwrap_or("127.0.0.1");
-        let rpc_url = format!("http://{}:1248", host);
-        let provider = Arc::new(Provider::<Http>::try_from(rpc_url.clone())?);
-        let client = Self { provider, rpc_url };
-
-        client.switch_network(chain_id).await?;
+    pub async fn new(chain_id: U256, host: Option<&str>, port: Option<u16>) -> Result<Self> {
+        FrameClient::builder()
+            .host(host.unwrap_or(DEFAULT_HOST))
+            .port(port.unwrap_or(DEFAULT_PORT))
+            .chain_id(chain_id)
+            .build()
+            .await
+    }
 
+    /// Connects to Frame on the default port without switching networks, leaving whatever chain
+    /// Frame has active. Unlike [`FrameClient::new`], this never opens a prompt in Frame, which
+    /// suits applications that only read from the current chain.
+    ///
+    /// The connection is checked with [`FrameClient::ping`], so a client is only returned if
+    /// Frame is running. Use [`FrameClient::get_chain_id`] to find out which chain is active.
+    ///
+    /// # Parameters
+    /// - `host`: The host address of the Frame wallet's RPC endpoint. Defaults to 127.0.0.1.
+    ///
+    /// # Returns
+    /// Returns a `Result` wrapping a new `FrameClient` instance connected to the active chain.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::try_connect(None).await?;
+    ///     println!("Frame is on chain {}", client.get_chain_id().await?);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the host is not a valid IP address or hostname, and
+    /// `FrameError::ConnectionFailed` if Frame cannot be reached.
+    pub async fn try_connect(host: Option<&str>) -> Result<Self> {
+        let client = FrameClient::builder()
+            .host(host.unwrap_or(DEFAULT_HOST))
+            .build()
+            .await?;
+        client.ping().await?;
         Ok(client)
     }
 
+    /// Returns a [`FrameClientBuilder`] for configuring a `FrameClient` with chainable methods.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::builder()
+    ///         .chain_id(U256::from(1))
+    ///         .port(9000)
+    ///         .build()
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn builder() -> FrameClientBuilder {
+        FrameClientBuilder::new()
+    }
+}
+
+impl FrameClient<Ws> {
+    /// Creates a new `FrameClient` backed by a WebSocket provider connected to Frame's
+    /// `ws://` endpoint, and switches the network to the specified `chain_id`.
+    ///
+    /// The WebSocket provider supports subscriptions such as `eth_subscribe`. The network switch
+    /// and other wallet requests are still sent over HTTP to the same host and port.
+    ///
+    /// # Parameters
+    /// - `chain_id`: The chain ID of the Ethereum network you want to connect to.
+    /// - `host`: The host address of the Frame wallet's RPC endpoint. Defaults to 127.0.0.1.
+    /// - `port`: The port of the Frame wallet's RPC endpoint. Defaults to 1248.
+    ///
+    /// # Returns
+    /// Returns a `Result` wrapping a new WebSocket-backed `FrameClient` instance.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new_ws(U256::from(1), None, None).await?;
+    ///     println!("Current Chain ID: {}", client.get_chain_id().await?);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the WebSocket connection cannot be established or if the network
+    /// switch request fails.
+    pub async fn new_ws(chain_id: U256, host: Option<&str>, port: Option<u16>) -> Result<Self> {
+        FrameClient::builder()
+            .host(host.unwrap_or(DEFAULT_HOST))
+            .port(port.unwrap_or(DEFAULT_PORT))
+            .chain_id(chain_id)
+            .build_ws()
+            .await
+    }
+}
+
+#[cfg(test)]
+impl FrameClient<ethers::providers::MockProvider> {
+    /// Creates a client backed by a `MockProvider`, for tests that don't need a running Frame.
+    pub(crate) fn mocked() -> (Self, ethers::providers::MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        // Poll quickly so tests awaiting a `PendingTransaction` finish promptly.
+        let provider = provider.interval(Duration::from_millis(10));
+        let rpc_url = format!("http://{}:{}", DEFAULT_HOST, DEFAULT_PORT);
+        let client = Self {
+            provider: Arc::new(provider),
+            rpc: FrameTransport::new(rpc_url.parse().unwrap(), Default::default()),
+            rpc_url,
+            prompt_queue: Default::default(),
+            allow_unsafe_signing: false,
+            receipt_timeout: None,
+            nonces: None,
+            multicall_address: crate::multicall::MULTICALL3_ADDRESS,
+            auto_access_list: false,
+            request_timeout: None,
+            simulate_before_send: false,
+            before_send: Default::default(),
+            policy: Default::default(),
+            expected_chain_id: Default::default(),
+            allow_chain_drift: false,
+            auto_resubmit: false,
+            dropped_after_polls: crate::pending::DEFAULT_DROPPED_AFTER_POLLS,
+            drop_stats: Default::default(),
+        };
+        (client, mock)
+    }
+
+    /// Like [`FrameClient::mocked`], but also serves wallet requests from a local HTTP server
+    /// that answers the n-th request with the n-th JSON-RPC response body in `responses`.
+    pub(crate) async fn mocked_with_rpc(
+        responses: Vec<Value>,
+    ) -> (Self, ethers::providers::MockProvider) {
+        let responses = responses
+            .into_iter()
+            .map(|response| (200, response.to_string()))
+            .collect();
+        let rpc_url = serve_http(responses).await;
+
+        let (mut client, mock) = Self::mocked();
+        client.rpc = FrameTransport::new(rpc_url.parse().unwrap(), Default::default());
+        client.rpc_url = rpc_url;
+        (client, mock)
+    }
+}
+
+/// Starts a local HTTP server that answers the n-th request with the n-th status and body in
+/// `responses`, and returns its URL.
+#[cfg(test)]
+pub(crate) async fn serve_http(responses: Vec<(u16, String)>) -> String {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    let listener = TcpListener::bind((DEFAULT_HOST, 0)).await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        for (status, body) in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+
+            // Read the whole request so closing the socket doesn't reset the connection.
+            let mut request = Vec::new();
+            let mut buf = [0; 4096];
+            loop {
+                let read = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..read]);
+                let request = String::from_utf8_lossy(&request);
+                if let Some((head, body)) = request.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|len| len.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or_default();
+                    if body.len() >= length {
+                        break;
+                    }
+                }
+                if read == 0 {
+                    break;
+                }
+            }
+
+            let response = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    url
+}
+
+/// Queues the responses of polling for `receipt`: the mined transaction, then the receipt.
+#[cfg(test)]
+pub(crate) fn push_receipt(mock: &ethers::providers::MockProvider, receipt: TransactionReceipt) {
+    let transaction = ethers::types::Transaction {
+        hash: receipt.transaction_hash,
+        block_number: receipt.block_number,
+        ..Default::default()
+    };
+    mock.push(receipt).unwrap();
+    mock.push(transaction).unwrap();
+}
+
+/// Queues the responses of a transaction that is mined in block 1 as soon as it is sent.
+#[cfg(test)]
+pub(crate) fn push_mined(mock: &ethers::providers::MockProvider, tx_hash: H256) {
+    push_receipt(
+        mock,
+        TransactionReceipt {
+            transaction_hash: tx_hash,
+            block_number: Some(1.into()),
+            ..Default::default()
+        },
+    );
+    mock.push(tx_hash).unwrap();
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Returns the URL of Frame's HTTP RPC endpoint, to which wallet requests are sent.
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// Checks that Frame is running and reachable by sending it a `net_version` request, and
+    /// measures the round trip.
+    ///
+    /// The request skips the prompt queue and gives up after [`PING_TIMEOUT`]. A JSON-RPC error
+    /// response still counts as a response, since Frame answered it.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the round-trip time of the request.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::builder().build().await?;
+    ///     match client.ping().await {
+    ///         Ok(latency) => println!("Frame responded in {:?}", latency),
+    ///         Err(err) => println!("Frame not detected: {}", err),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `FrameError::ConnectionFailed` if Frame cannot be reached or doesn't respond in
+    /// time, or `FrameError::Timeout` if the client's request timeout expires first.
+    pub async fn ping(&self) -> Result<Duration> {
+        let started = Instant::now();
+        let response = tokio::time::timeout(
+            PING_TIMEOUT,
+            self.send_rpc::<Value>("net_version", json!([])),
+        )
+        .await
+        .map_err(|_| {
+            FrameError::ConnectionFailed(format!("Frame did not respond within {:?}", PING_TIMEOUT))
+        })?;
+        match response {
+            Ok(_) | Err(FrameError::UserRejected) | Err(FrameError::RpcError { .. }) => {
+                Ok(started.elapsed())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns `true` if Frame is running and reachable, as checked by [`FrameClient::ping`].
+    pub async fn is_connected(&self) -> bool {
+        self.ping().await.is_ok()
+    }
+
     /// Retrieves the chain ID of the currently connected Ethereum network.
     ///
     /// This method queries the connected Ethereum node (through Frame's RPC endpoint)
@@ -81,7 +423,7 @@ impl FrameClient {
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let chain_id = U256::from(1); // Example chain_id, e.g., Ethereum Mainnet
-    ///     let client = FrameClient::new(chain_id, None).await?;
+    ///     let client = FrameClient::new(chain_id, None, None).await?;
     ///     
     ///     let current_chain_id = client.get_chain_id().await?;
     ///     println!("Current Chain ID: {}", current_chain_id);
@@ -101,11 +443,13 @@ impl FrameClient {
 
     /// Attempts to switch the connected Ethereum network in the Frame wallet to the specified `chain_id`.
     ///
+    /// Once switched, `chain_id` is the chain transactions are expected to be sent on, see
+    /// [`FrameClient::expected_chain_id`].
     ///
     /// # Parameters
     /// - `chain_id`: The chain ID of the Ethereum network you wish to switch to, provided as a `U256`.
-    /// The `chain_id` should be in hexadecimal format, but this method will handle the conversion
-    /// for you. For example, to switch to the Ethereum Mainnet, you would pass `U256::from(1)`.
+    ///   The `chain_id` should be in hexadecimal format, but this method will handle the conversion
+    ///   for you. For example, to switch to the Ethereum Mainnet, you would pass `U256::from(1)`.
     ///
     /// # Returns
     /// Returns `Ok(())` if the network switch request was successfully sent and acknowledged by the
@@ -120,7 +464,7 @@ impl FrameClient {
     ///
     /// #[tokio::main]
     /// async fn main() -> anyhow::Result<()> {
-    ///     let client = FrameClient::new(U256::from(1), None).await?; // Assuming Ethereum Mainnet
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?; // Assuming Ethereum Mainnet
     ///     let rinkeby_chain_id = U256::from(42161); // Arbitrum One
     ///
     ///     client.switch_network(rinkeby_chain_id).await?;
@@ -132,31 +476,291 @@ impl FrameClient {
     ///
     /// # Errors
     /// If the network switch cannot be completed, an error is returned with details about the failure.
-    /// This might occur if the Frame wallet is not accessible. It will not error for an invalid chain ID.
+    /// Returns `FrameError::ConnectionFailed` if the Frame wallet is not accessible and
+    /// `FrameError::ChainNotFound` if the chain is not configured in Frame, in which case it can
+    /// be added with [`FrameClient::add_network`]. If the user rejects the switch in Frame, the
+    /// error is `FrameError::UserRejected`, even though Frame answers with HTTP 200. Other errors
+    /// reported by Frame are returned as `FrameError::RpcError`.
     pub async fn switch_network(&self, chain_id: U256) -> Result<()> {
-        let client = Client::new();
         let chain_id_hex = format!("{:#x}", chain_id);
 
         let params = json!([{
             "chainId": chain_id_hex,
         }]);
 
-        let response = client
-            .post(self.rpc_url.clone())
-            .json(&json!({
-                "jsonrpc": "2.0",
-                "method": "wallet_switchEthereumChain",
-                "params": params,
-                "id": "1"
-            }))
-            .send()
+        match self
+            .request::<Value>("wallet_switchEthereumChain", params)
+            .await
+        {
+            Ok(_) => {
+                self.sync_nonces().await;
+                *self.expected_chain_id.write().await = Some(chain_id);
+                Ok(())
+            }
+            Err(FrameError::RpcError {
+                code: UNRECOGNIZED_CHAIN_CODE,
+                ..
+            }) => Err(FrameError::ChainNotFound(chain_id)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the chain ID the client expects Frame to be connected to: the chain it was built
+    /// for, or the chain of the last successful [`FrameClient::switch_network`].
+    ///
+    /// Every send checks that Frame is still connected to this chain, since it can be switched
+    /// in the Frame UI at any time, and fails with `FrameError::ChainMismatch` otherwise. The
+    /// check is skipped if no chain is expected, e.g. for a client created with
+    /// [`FrameClient::try_connect`], or if chain drift is allowed with
+    /// [`FrameClientBuilder::allow_chain_drift`](crate::builder::FrameClientBuilder::allow_chain_drift).
+    pub async fn expected_chain_id(&self) -> Option<U256> {
+        *self.expected_chain_id.read().await
+    }
+
+    /// Checks that Frame is connected to the expected chain, querying its current chain ID.
+    pub(crate) async fn ensure_expected_chain(&self) -> Result<()> {
+        if self.allow_chain_drift {
+            return Ok(());
+        }
+        let Some(expected) = self.expected_chain_id().await else {
+            return Ok(());
+        };
+        let actual = self.get_chain_id().await?;
+        if actual != expected {
+            return Err(FrameError::ChainMismatch { expected, actual });
+        }
+        Ok(())
+    }
+
+    /// Adds a new network to the Frame wallet's network list via `wallet_addEthereumChain` (EIP-3085).
+    ///
+    /// # Parameters
+    /// - `config`: The `ChainConfig` describing the network to add, including its chain ID, name,
+    ///   native currency, RPC URLs and optional block explorer and icon URLs.
+    ///
+    /// # Returns
+    /// Returns `Ok(())` if Frame accepted the new network.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    /// use frame_rs::types::{ChainConfig, NativeCurrency};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let config = ChainConfig {
+    ///         chain_id: U256::from(8453),
+    ///         chain_name: "Base".to_string(),
+    ///         native_currency: NativeCurrency {
+    ///             name: "Ether".to_string(),
+    ///             symbol: "ETH".to_string(),
+    ///             decimals: 18,
+    ///         },
+    ///         rpc_urls: vec!["https://mainnet.base.org".to_string()],
+    ///         block_explorer_urls: Some(vec!["https://basescan.org".to_string()]),
+    ///         icon_urls: None,
+    ///     };
+    ///
+    ///     client.add_network(&config).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the Frame wallet is not accessible, or a `FrameError::RpcError` if Frame
+    /// rejects the request, for example because the chain already exists with conflicting
+    /// parameters. If the user declines the request, the error is `FrameError::UserRejected`.
+    pub async fn add_network(&self, config: &ChainConfig) -> Result<()> {
+        self.request::<Value>("wallet_addEthereumChain", json!([config]))
             .await?;
+        Ok(())
+    }
+
+    /// Adds a new network to the Frame wallet and then switches to it.
+    ///
+    /// This is a convenience wrapper around [`FrameClient::add_network`] followed by
+    /// [`FrameClient::switch_network`] with the chain ID from `config`.
+    ///
+    /// # Parameters
+    /// - `config`: The `ChainConfig` describing the network to add and switch to.
+    ///
+    /// # Returns
+    /// Returns `Ok(())` if the network was added and the switch request was sent successfully.
+    ///
+    /// # Errors
+    /// Returns an error if either the add or the switch request fails.
+    pub async fn add_network_and_switch(&self, config: &ChainConfig) -> Result<()> {
+        self.add_network(config).await?;
+        self.switch_network(config.chain_id).await
+    }
+
+    /// Switches to the network described by `config`, adding it to Frame first if it is not
+    /// configured yet.
+    ///
+    /// The switch is attempted first, so a network that already exists in Frame is not added
+    /// again. If Frame reports the chain as unrecognized (EIP-3326 code 4902), the network is
+    /// added with [`FrameClient::add_network`] and the switch is retried.
+    ///
+    /// # Parameters
+    /// - `config`: The `ChainConfig` describing the network to switch to.
+    ///
+    /// # Returns
+    /// Returns `Ok(())` once Frame has switched to the network.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    /// use frame_rs::types::{ChainConfig, NativeCurrency};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let config = ChainConfig {
+    ///         chain_id: U256::from(8453),
+    ///         chain_name: "Base".to_string(),
+    ///         native_currency: NativeCurrency {
+    ///             name: "Ether".to_string(),
+    ///             symbol: "ETH".to_string(),
+    ///             decimals: 18,
+    ///         },
+    ///         rpc_urls: vec!["https://mainnet.base.org".to_string()],
+    ///         block_explorer_urls: Some(vec!["https://basescan.org".to_string()]),
+    ///         icon_urls: None,
+    ///     };
+    ///
+    ///     client.ensure_network(&config).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the switch fails for another reason than an unrecognized chain, or
+    /// if adding the network or the second switch fails. If the user declines either request,
+    /// the error is `FrameError::UserRejected`.
+    pub async fn ensure_network(&self, config: &ChainConfig) -> Result<()> {
+        match self.switch_network(config.chain_id).await {
+            Err(FrameError::ChainNotFound(_)) => self.add_network_and_switch(config).await,
+            result => result,
+        }
+    }
 
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            bail!("Failed to switch network: {}", error_text);
+    /// Asks Frame to add a token to the user's asset list with `wallet_watchAsset` (EIP-747).
+    ///
+    /// # Parameters
+    /// - `asset_type`: The kind of asset, e.g. `AssetType::Erc20`.
+    /// - `options`: The address, symbol, decimals and optional logo URL of the token.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps `true` if the user added the token and
+    /// `false` if they declined.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    /// use frame_rs::types::{AssetType, WatchAssetOptions};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let options = WatchAssetOptions {
+    ///         address: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse()?,
+    ///         symbol: "USDC".to_string(),
+    ///         decimals: 6,
+    ///         image: None,
+    ///     };
+    ///
+    ///     if client.watch_asset(AssetType::Erc20, options).await? {
+    ///         println!("USDC was added to Frame");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the Frame wallet is not accessible, or a `FrameError::RpcError` if
+    /// Frame rejects the request, for example because the token address is invalid. Declining
+    /// the request in Frame is not an error.
+    pub async fn watch_asset(
+        &self,
+        asset_type: AssetType,
+        options: WatchAssetOptions,
+    ) -> Result<bool> {
+        let params = json!({ "type": asset_type, "options": options });
+        match self.request("wallet_watchAsset", params).await {
+            Err(FrameError::UserRejected) => Ok(false),
+            result => result,
         }
+    }
+
+    /// Requests permissions from Frame with `wallet_requestPermissions` (EIP-2255).
+    ///
+    /// # Parameters
+    /// - `permissions`: The permissions to request, e.g. `PermissionRequest::new("eth_accounts")`.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the permissions granted by the user.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    /// use frame_rs::types::PermissionRequest;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let granted = client
+    ///         .request_permissions(&[PermissionRequest::new("eth_accounts")])
+    ///         .await?;
+    ///     for permission in granted {
+    ///         println!("Granted {}", permission.parent_capability);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the Frame wallet is not accessible or the request fails. If the user
+    /// declines the request, the error is `FrameError::UserRejected`.
+    pub async fn request_permissions(
+        &self,
+        permissions: &[PermissionRequest],
+    ) -> Result<Vec<Permission>> {
+        let requested: serde_json::Map<String, Value> = permissions
+            .iter()
+            .map(|permission| (permission.method.clone(), json!({})))
+            .collect();
+        self.request("wallet_requestPermissions", json!([requested]))
+            .await
+    }
+
+    /// Retrieves the permissions Frame has granted to this client with `wallet_getPermissions`
+    /// (EIP-2255).
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the granted permissions.
+    ///
+    /// # Errors
+    /// Returns an error if the Frame wallet is not accessible or the request fails.
+    pub async fn get_permissions(&self) -> Result<Vec<Permission>> {
+        self.request("wallet_getPermissions", json!([])).await
+    }
 
+    /// Revokes a permission with `wallet_revokePermissions`.
+    ///
+    /// # Parameters
+    /// - `permission_id`: The permission to revoke, identified like in the request by the RPC
+    ///   method it grants access to, i.e. its `parent_capability`, e.g. `eth_accounts`.
+    ///
+    /// # Errors
+    /// Returns an error if the Frame wallet is not accessible or the request fails.
+    pub async fn revoke_permissions(&self, permission_id: &str) -> Result<()> {
+        self.request::<Value>("wallet_revokePermissions", json!([{ permission_id: {} }]))
+            .await?;
         Ok(())
     }
 
@@ -182,7 +786,7 @@ impl FrameClient {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<()> {
-    ///     let client = FrameClient::new(U256::from(1), None).await?;
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
     ///     let from: Address = "0x...".parse()?;
     ///     let to: Address = "0x...".parse()?;
     ///     let amount = U256::from(1000000000000000000u64); // 1 ETH in Wei
@@ -195,61 +799,2476 @@ impl FrameClient {
     ///
     /// # Errors
     /// Returns an error if the transaction fails to be sent or if there is an issue with
-    /// the transaction's execution.
+    /// the transaction's execution; a reverted transaction is returned as
+    /// `FrameError::TransactionReverted`, carrying its hash. If the client was built with a
+    /// [`FrameClientBuilder::receipt_timeout`] that expires first, the error is
+    /// `FrameError::Timeout`, carrying the hash if the transaction was already submitted.
     pub async fn send_gas_token(&self, from: Address, to: Address, amount: U256) -> Result<H256> {
-        let tx = TransactionRequest::new().from(from).to(to).value(amount);
-        let pending_tx = self.provider.send_transaction(tx, None).await?;
-        let tx_receipt = pending_tx.await?;
-        if let Some(tx_hash) = tx_receipt {
-            return Ok(tx_hash.transaction_hash);
-        }
-
-        bail!("Tx failed to send");
+        self.send_gas_token_with_strategy(from, to, amount, GasStrategy::Auto)
+            .await
     }
 
-    /// Retrieves a list of addresses owned by the connected wallet.
+    /// Sends the native gas token from one address to another with explicit gas pricing.
     ///
-    /// This asynchronous method queries the connected Ethereum provider (e.g., Frame) for
-    /// the list of accounts it manages.
+    /// This behaves like [`FrameClient::send_gas_token`], but lets the caller choose between a
+    /// legacy transaction with a fixed gas price, an EIP-1559 (type 2) transaction with explicit
+    /// fee caps, or leaving the pricing to the provider.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` from which the gas token will be sent.
+    /// - `to`: The `Address` to which the gas token will be sent.
+    /// - `amount`: The amount of the gas token to send, specified in Wei as a `U256`.
+    /// - `strategy`: The `GasStrategy` used to price the transaction.
     ///
     /// # Returns
-    /// Returns a `Result` that, on success, wraps a vector of `Address`es representing the
-    /// accounts managed by the connected provider. If the query fails, an error is returned
-    /// with details about the failure.
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash of the sent transaction.
     ///
     /// # Examples
     /// ```no_run
+    /// use ethers::types::{Address, U256};
     /// use frame_rs::client::FrameClient;
-    /// use anyhow::Result;
-    /// use ethers::types::U256;
+    /// use frame_rs::types::GasStrategy;
     ///
     /// #[tokio::main]
-    /// async fn main() -> Result<()> {
-    ///     let client = FrameClient::new(U256::from(1), None).await?;
-    ///     let accounts = client.get_accounts().await?;
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///     let strategy = GasStrategy::Eip1559 {
+    ///         max_fee: U256::from(30_000_000_000u64),
+    ///         priority_fee: U256::from(1_000_000_000u64),
+    ///     };
     ///
-    ///     for account in accounts {
-    ///         println!("Account address: {:?}", account);
-    ///     }
+    ///     let tx_hash = client
+    ///         .send_gas_token_with_strategy(from, to, U256::exp10(18), strategy)
+    ///         .await?;
+    ///     println!("Transaction hash: {:?}", tx_hash);
     ///     Ok(())
     /// }
     /// ```
     ///
     /// # Errors
-    /// Returns an error if there is an issue fetching the accounts from the connected provider.
-    pub async fn get_accounts(&self) -> Result<Vec<Address>> {
-        let accounts = self.provider.get_accounts().await?;
-        Ok(accounts)
+    /// Returns an error if the transaction fails to be sent or if there is an issue with
+    /// the transaction's execution.
+    pub async fn send_gas_token_with_strategy(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+        strategy: GasStrategy,
+    ) -> Result<H256> {
+        let tx = strategy.apply(TransactionRequest::new().from(from).to(to).value(amount));
+        self.send_and_wait(tx).await
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Sends the native gas token from one address to another with explicit gas, nonce and fee
+    /// overrides.
+    ///
+    /// This behaves like [`FrameClient::send_gas_token`], but applies the given `SendOptions`
+    /// to the transaction before it is sent. Overrides that are left unset are filled in as
+    /// usual, so `SendOptions::default()` sends the same transaction as `send_gas_token`.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` from which the gas token will be sent.
+    /// - `to`: The `Address` to which the gas token will be sent.
+    /// - `amount`: The amount of the gas token to send, specified in Wei as a `U256`.
+    /// - `opts`: The `SendOptions` to apply.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash of the sent transaction.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::{types::{Address, U256}, utils::parse_units};
+    /// use frame_rs::{client::FrameClient, types::SendOptions};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     // Replace a stuck transaction with nonce 12 by paying a higher fee.
+    ///     let opts = SendOptions {
+    ///         nonce: Some(U256::from(12)),
+    ///         gas_limit: Some(U256::from(21_000)),
+    ///         max_fee_per_gas: Some(parse_units(60, "gwei")?.into()),
+    ///         max_priority_fee_per_gas: Some(parse_units(3, "gwei")?.into()),
+    ///         ..Default::default()
+    ///     };
+    ///     let tx_hash = client
+    ///         .send_gas_token_with_opts(from, to, U256::exp10(18), opts)
+    ///         .await?;
+    ///     println!("Transaction hash: {:?}", tx_hash);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if `gas_price` is combined with an EIP-1559 fee, if the transaction fails
+    /// to be sent or if no receipt is returned for it.
+    pub async fn send_gas_token_with_opts(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+        opts: SendOptions,
+    ) -> Result<H256> {
+        let tx = opts.apply(TransactionRequest::new().from(from).to(to).value(amount))?;
+        self.send_and_wait(tx).await
+    }
+
+    /// Sends the native gas token from one address to another with an explicit nonce.
+    ///
+    /// This behaves like [`FrameClient::send_gas_token`], but sets the transaction's nonce
+    /// instead of letting Frame choose it, which avoids collisions when several transactions are
+    /// sent in quick succession and allows replacing a stuck transaction.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` from which the gas token will be sent.
+    /// - `to`: The `Address` to which the gas token will be sent.
+    /// - `amount`: The amount of the gas token to send, specified in Wei as a `U256`.
+    /// - `nonce`: The nonce of the transaction. `None` lets Frame choose it.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash of the sent transaction.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let recipients: Vec<Address> = vec!["0x...".parse()?, "0x...".parse()?];
+    ///
+    ///     let mut nonce = client.get_transaction_count(from, true).await?;
+    ///     for to in recipients {
+    ///         client
+    ///             .send_gas_token_with_nonce(from, to, U256::exp10(16), Some(nonce))
+    ///             .await?;
+    ///         nonce += U256::one();
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    pub async fn send_gas_token_with_nonce(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+        nonce: Option<U256>,
+    ) -> Result<H256> {
+        let mut tx = TransactionRequest::new().from(from).to(to).value(amount);
+        tx.nonce = nonce;
+        self.send_and_wait(tx).await
+    }
+
+    /// Sends the native gas token from one address to another in an EIP-1559 (type 2)
+    /// transaction with explicit fee caps.
+    ///
+    /// This is a shorthand for [`FrameClient::send_gas_token_with_strategy`] with
+    /// `GasStrategy::Eip1559`.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` from which the gas token will be sent.
+    /// - `to`: The `Address` to which the gas token will be sent.
+    /// - `amount`: The amount of the gas token to send, specified in Wei as a `U256`.
+    /// - `max_fee`: The maximum total fee per gas, in Wei.
+    /// - `max_priority_fee`: The maximum priority fee (tip) per gas, in Wei.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash of the sent transaction.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::{types::{Address, U256}, utils::parse_units};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///     let max_fee = parse_units(40, "gwei")?.into();
+    ///     let max_priority_fee = parse_units(2, "gwei")?.into();
+    ///
+    ///     let tx_hash = client
+    ///         .send_gas_token_1559(from, to, U256::exp10(18), max_fee, max_priority_fee)
+    ///         .await?;
+    ///     println!("Transaction hash: {:?}", tx_hash);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    pub async fn send_gas_token_1559(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+        max_fee: U256,
+        max_priority_fee: U256,
+    ) -> Result<H256> {
+        let strategy = GasStrategy::Eip1559 {
+            max_fee,
+            priority_fee: max_priority_fee,
+        };
+        self.send_gas_token_with_strategy(from, to, amount, strategy)
+            .await
+    }
+
+    /// Sends the native gas token from one address to another in an EIP-1559 (type 2)
+    /// transaction, estimating the fee caps when they aren't given.
+    ///
+    /// Without `fees`, the caps are estimated with [`FrameClient::estimate_fee_overrides`]. On
+    /// networks that don't support EIP-1559, i.e. whose latest block has no base fee, a legacy
+    /// transaction is sent instead. Its gas price is the `max_fee_per_gas` of `fees`, or, without
+    /// `fees`, set like for [`FrameClient::send_gas_token`].
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` from which the gas token will be sent.
+    /// - `to`: The `Address` to which the gas token will be sent.
+    /// - `amount`: The amount of the gas token to send, specified in Wei as a `U256`.
+    /// - `fees`: The `FeeOverrides` to use. `None` estimates them.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash of the sent transaction.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let tx_hash = client
+    ///         .send_gas_token_eip1559(from, to, U256::exp10(18), None)
+    ///         .await?;
+    ///     println!("Transaction hash: {:?}", tx_hash);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the fees cannot be estimated, if the transaction fails to be sent or
+    /// if no receipt is returned for it.
+    pub async fn send_gas_token_eip1559(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+        fees: Option<FeeOverrides>,
+    ) -> Result<H256> {
+        let strategy = match fees {
+            Some(fees) => match self.get_latest_block().await?.base_fee_per_gas {
+                Some(_) => fees.into(),
+                None => GasStrategy::Legacy(fees.max_fee_per_gas),
+            },
+            None => match self.estimate_fee_overrides().await? {
+                Some(fees) => fees.into(),
+                None => GasStrategy::Auto,
+            },
+        };
+        self.send_gas_token_with_strategy(from, to, amount, strategy)
+            .await
+    }
+
+    /// Sends the native gas token from one address to another and returns the full receipt.
+    ///
+    /// This behaves like [`FrameClient::send_gas_token`], but returns the `TransactionReceipt`
+    /// instead of only the hash, so callers can inspect the status, gas used and effective gas
+    /// price of the transfer.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` from which the gas token will be sent.
+    /// - `to`: The `Address` to which the gas token will be sent.
+    /// - `amount`: The amount of the gas token to send, specified in Wei as a `U256`.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `TransactionReceipt` of the transfer.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let receipt = client.send_gas_token_with_receipt(from, to, U256::exp10(18)).await?;
+    ///     println!("Transfer succeeded using {:?} gas", receipt.gas_used);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    /// If the transfer reverted, the error is `FrameError::TransactionReverted`.
+    pub async fn send_gas_token_with_receipt(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<TransactionReceipt> {
+        let tx = TransactionRequest::new().from(from).to(to).value(amount);
+        self.send_and_wait_for_receipt(tx).await
+    }
+
+    /// Sends the native gas token and waits for a number of confirmations before returning.
+    ///
+    /// This behaves like [`FrameClient::send_gas_token_with_receipt`], but only returns once
+    /// `confirmations` blocks, including the one that mined the transaction, have been produced.
+    /// Use more confirmations on chains where reorgs are common.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` from which the gas token will be sent.
+    /// - `to`: The `Address` to which the gas token will be sent.
+    /// - `amount`: The amount of the gas token to send, specified in Wei as a `U256`.
+    /// - `confirmations`: The number of confirmations to wait for.
+    /// - `timeout`: How long to wait for the transaction to be approved in Frame and confirmed.
+    ///   `None` uses the client's receipt timeout, if one was set with
+    ///   [`FrameClientBuilder::receipt_timeout`].
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `TransactionReceipt` of the transfer.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::client::FrameClient;
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let receipt = client
+    ///         .send_gas_token_confirmed(from, to, U256::exp10(18), 12, Some(Duration::from_secs(300)))
+    ///         .await?;
+    ///     println!("Confirmed in block {:?}", receipt.block_number);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    /// Returns `FrameError::Timeout` if the confirmations are not reached within `timeout`. The
+    /// error carries the transaction hash if the transaction was submitted before the deadline.
+    pub async fn send_gas_token_confirmed(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+        confirmations: usize,
+        timeout: Option<Duration>,
+    ) -> Result<TransactionReceipt> {
+        let tx = TransactionRequest::new().from(from).to(to).value(amount);
+        self.send_and_confirm(tx, confirmations, timeout).await
+    }
+
+    /// Sends a transaction with arbitrary calldata to a contract through Frame.
+    ///
+    /// This is the generic counterpart to [`FrameClient::send_gas_token`] for state-changing
+    /// contract calls such as `approve` or `stake`. The calldata is encoded by the caller, e.g.
+    /// with ethers' ABI tools. Like the other sending methods, it waits for the receipt.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` that signs and sends the transaction.
+    /// - `to`: The `Address` of the contract to call.
+    /// - `data`: The calldata, including the 4-byte function selector.
+    /// - `value`: The amount of the gas token to send along, in Wei. `None` sends no value.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::{
+    ///     abi::{encode, Token},
+    ///     types::{Address, U256},
+    ///     utils::id,
+    /// };
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///     let token: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse()?;
+    ///     let spender: Address = "0x...".parse()?;
+    ///
+    ///     let mut data = id("approve(address,uint256)").to_vec();
+    ///     data.extend(encode(&[Token::Address(spender), Token::Uint(U256::MAX)]));
+    ///     let tx_hash = client
+    ///         .send_transaction_raw(from, token, data.into(), None)
+    ///         .await?;
+    ///     println!("Transaction hash: {:?}", tx_hash);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    /// If the user declines the transaction in Frame, the error is `FrameError::UserRejected`.
+    pub async fn send_transaction_raw(
+        &self,
+        from: Address,
+        to: Address,
+        data: Bytes,
+        value: Option<U256>,
+    ) -> Result<H256> {
+        let mut tx = TransactionRequest::new().from(from).to(to).data(data);
+        tx.value = value;
+        self.send_and_wait(tx).await
+    }
+
+    /// Sends a fully specified transaction through Frame.
+    ///
+    /// The request is forwarded as given, including any `data`, `gas`, `gas_price` and `nonce`
+    /// set by the caller; fields left unset are filled in by the provider, as for
+    /// [`FrameClient::send_gas_token`]. If `from` is unset, the first account returned by
+    /// [`FrameClient::get_accounts`] is used. Like the other sending methods, it waits for the
+    /// receipt.
+    ///
+    /// # Parameters
+    /// - `tx`: The `TransactionRequest` to send.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, TransactionRequest, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let contract: Address = "0x...".parse()?;
+    ///
+    ///     let tx = TransactionRequest::new()
+    ///         .to(contract)
+    ///         .data(vec![0xd0, 0xe3, 0x0d, 0xb0]) // deposit()
+    ///         .value(U256::exp10(17))
+    ///         .gas(60_000);
+    ///     let tx_hash = client.send_transaction(tx).await?;
+    ///     println!("Transaction hash: {:?}", tx_hash);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if `from` is unset and Frame has no accounts, if the transaction fails
+    /// to be sent or if no receipt is returned for it. If the user declines the transaction in
+    /// Frame, the error is `FrameError::UserRejected`.
+    pub async fn send_transaction(&self, mut tx: TransactionRequest) -> Result<H256> {
+        if tx.from.is_none() {
+            match self.get_accounts().await?.first() {
+                Some(from) => tx.from = Some(*from),
+                None => bail!("Frame has no accounts to send the transaction from"),
+            }
+        }
+        self.send_and_wait(tx).await
+    }
+
+    /// Retrieves a list of addresses owned by the connected wallet.
+    ///
+    /// This asynchronous method queries the connected Ethereum provider (e.g., Frame) for
+    /// the list of accounts it manages.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps a vector of `Address`es representing the
+    /// accounts managed by the connected provider. If the query fails, an error is returned
+    /// with details about the failure.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use frame_rs::client::FrameClient;
+    /// use anyhow::Result;
+    /// use ethers::types::U256;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let accounts = client.get_accounts().await?;
+    ///
+    ///     for account in accounts {
+    ///         println!("Account address: {:?}", account);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if there is an issue fetching the accounts from the connected provider.
+    pub async fn get_accounts(&self) -> Result<Vec<Address>> {
+        let accounts = self.provider.get_accounts().await?;
+        Ok(accounts)
+    }
+
+    /// Asks the user to connect their accounts with `eth_requestAccounts` (EIP-1102).
+    ///
+    /// Unlike [`FrameClient::get_accounts`], which returns an empty list until the connection
+    /// is authorized, this opens a prompt in Frame if access hasn't been granted yet, and
+    /// returns the accounts once the user approves it.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `Address`es the user granted access to.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use frame_rs::{client::FrameClient, error::FrameError};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::try_connect(None).await?;
+    ///     match client.request_accounts().await {
+    ///         Ok(accounts) => println!("Connected {:?}", accounts),
+    ///         Err(FrameError::UserRejected) => println!("Connection was rejected in Frame"),
+    ///         Err(err) => return Err(err.into()),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the Frame wallet is not accessible. If the user declines the
+    /// connection in Frame, the error is `FrameError::UserRejected`.
+    pub async fn request_accounts(&self) -> Result<Vec<Address>> {
+        self.request("eth_requestAccounts", json!([])).await
+    }
+
+    /// Retrieves the balance of the network's gas token held by an address.
+    ///
+    /// # Parameters
+    /// - `address`: The `Address` whose balance is queried.
+    /// - `block`: The block to query the balance at, as a block number, tag or hash. `None`
+    ///   queries the latest block.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the balance in Wei.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{BlockId, BlockNumber, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let account = client.get_accounts().await?[0];
+    ///
+    ///     let block = BlockId::Number(BlockNumber::Number(19_000_000.into()));
+    ///     let balance = client.get_balance(account, Some(block)).await?;
+    ///     println!("Balance at block 19000000: {} wei", balance);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the balance cannot be fetched from the connected provider.
+    pub async fn get_balance(&self, address: Address, block: Option<BlockId>) -> Result<U256> {
+        let balance = self.provider.get_balance(address, block).await?;
+        Ok(balance)
+    }
+
+    /// Retrieves the balance of an address at the latest block.
+    ///
+    /// This is a shorthand for [`FrameClient::get_balance`] with `block` set to `None`.
+    ///
+    /// # Errors
+    /// Returns an error if the balance cannot be fetched from the connected provider.
+    pub async fn get_balance_latest(&self, address: Address) -> Result<U256> {
+        self.get_balance(address, None).await
+    }
+
+    /// Retrieves the nonce of an address, i.e. the number of transactions it has sent.
+    ///
+    /// # Parameters
+    /// - `address`: The `Address` whose nonce is queried.
+    /// - `block`: The block to query the nonce at, as a block number, tag or hash. `None`
+    ///   queries the latest block, which ignores transactions still in the mempool.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the transaction count of `address`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let account = client.get_accounts().await?[0];
+    ///
+    ///     let confirmed = client.get_confirmed_nonce(account).await?;
+    ///     let pending = client.get_pending_nonce(account).await?;
+    ///     println!("{} transactions are pending", pending - confirmed);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the nonce cannot be fetched from the connected provider.
+    pub async fn get_nonce(&self, address: Address, block: Option<BlockId>) -> Result<U256> {
+        let nonce = self.provider.get_transaction_count(address, block).await?;
+        Ok(nonce)
+    }
+
+    /// Retrieves the nonce of an address including its pending transactions.
+    ///
+    /// This is a shorthand for [`FrameClient::get_nonce`] at the `pending` block, which is the
+    /// nonce the next transaction from `address` should use.
+    ///
+    /// # Errors
+    /// Returns an error if the nonce cannot be fetched from the connected provider.
+    pub async fn get_pending_nonce(&self, address: Address) -> Result<U256> {
+        self.get_nonce(address, Some(BlockNumber::Pending.into()))
+            .await
+    }
+
+    /// Retrieves the nonce of an address counting only mined transactions.
+    ///
+    /// This is a shorthand for [`FrameClient::get_nonce`] at the `latest` block. The difference
+    /// to [`FrameClient::get_pending_nonce`] is the number of transactions of `address` still
+    /// in the mempool. Neither reads the nonces cached by the client's nonce management.
+    ///
+    /// # Errors
+    /// Returns an error if the nonce cannot be fetched from the connected provider.
+    pub async fn get_confirmed_nonce(&self, address: Address) -> Result<U256> {
+        self.get_nonce(address, Some(BlockNumber::Latest.into()))
+            .await
+    }
+
+    /// Retrieves the transaction count of an address at the latest block, or including its
+    /// pending transactions if `pending` is `true`.
+    ///
+    /// With `pending` set, this is the nonce to use for the next transaction from `address`.
+    ///
+    /// # Errors
+    /// Returns an error if the count cannot be fetched from the connected provider.
+    pub async fn get_transaction_count(&self, address: Address, pending: bool) -> Result<U256> {
+        if pending {
+            self.get_pending_nonce(address).await
+        } else {
+            self.get_confirmed_nonce(address).await
+        }
+    }
+
+    /// Executes a read-only call with `eth_call`, without creating a transaction.
+    ///
+    /// This is the building block for contract reads and simulations: the raw return data is
+    /// returned so it can be decoded with the contract's ABI.
+    ///
+    /// # Parameters
+    /// - `tx`: The `TransactionRequest` describing the call, typically with `to` and `data` set.
+    /// - `block`: The block to execute the call against, as a block number, tag or hash. `None`
+    ///   uses the latest block.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the raw return data of the call.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::{
+    ///     abi::{decode, ParamType},
+    ///     types::{Address, TransactionRequest, U256},
+    /// };
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse()?;
+    ///
+    ///     // totalSupply()
+    ///     let tx = TransactionRequest::new().to(usdc).data(vec![0x18, 0x16, 0x0d, 0xdd]);
+    ///     let output = client.call(&tx, None).await?;
+    ///     let supply = decode(&[ParamType::Uint(256)], &output)?;
+    ///     println!("Total supply: {:?}", supply);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `FrameError::ExecutionReverted` with the revert reason and raw revert data if the
+    /// call reverts, or another error if the provider cannot execute it.
+    pub async fn call(&self, tx: &TransactionRequest, block: Option<BlockId>) -> Result<Bytes> {
+        let tx: TypedTransaction = tx.clone().into();
+        let output = self
+            .provider
+            .call(&tx, block)
+            .await
+            .map_err(FrameError::from_revert)?;
+        Ok(output)
+    }
+
+    /// Calls a contract at `to` with pre-encoded calldata against the latest block.
+    ///
+    /// This is a shorthand for [`FrameClient::call`] for the common case of reading a view
+    /// function, with the calldata encoded by the caller, e.g. with ethers' ABI tools.
+    ///
+    /// # Parameters
+    /// - `to`: The `Address` of the contract to call.
+    /// - `data`: The calldata, including the 4-byte function selector.
+    /// - `from`: The `Address` to use as `msg.sender`, for calls that depend on it. `None`
+    ///   leaves it to the node, which usually uses the zero address.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the raw return data of the call.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::{
+    ///     abi::{decode, ParamType},
+    ///     types::{Address, U256},
+    /// };
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse()?;
+    ///
+    ///     // totalSupply()
+    ///     let output = client
+    ///         .call_contract(usdc, vec![0x18, 0x16, 0x0d, 0xdd].into(), None)
+    ///         .await?;
+    ///     let supply = decode(&[ParamType::Uint(256)], &output)?;
+    ///     println!("Total supply: {:?}", supply);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `FrameError::ExecutionReverted` if the call reverts, or another error if the
+    /// provider cannot execute it.
+    pub async fn call_contract(
+        &self,
+        to: Address,
+        data: Bytes,
+        from: Option<Address>,
+    ) -> Result<Bytes> {
+        let mut tx = TransactionRequest::new().to(to).data(data);
+        tx.from = from;
+        self.call(&tx, None).await
+    }
+
+    /// Estimates the gas a transaction would use, without sending it.
+    ///
+    /// Transactions sent through Frame, e.g. by [`FrameClient::send_gas_token`], already have
+    /// their gas limit estimated when it is not set. This method lets the caller preview the
+    /// cost before asking the user to confirm.
+    ///
+    /// # Parameters
+    /// - `tx`: The `TransactionRequest` to estimate. It may be any transaction, including
+    ///   contract calls with `data`.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the estimated gas limit.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::{
+    ///     middleware::Middleware,
+    ///     types::{Address, TransactionRequest, U256},
+    /// };
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let tx = TransactionRequest::new().from(from).to(to).value(U256::exp10(18));
+    ///     let gas = client.estimate_gas(&tx).await?;
+    ///     let gas_price = client.provider.get_gas_price().await?;
+    ///     println!("Estimated cost: {} wei", gas * gas_price);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `FrameError::ExecutionReverted` with the revert reason if the transaction would
+    /// revert, or another error if the provider cannot estimate it.
+    pub async fn estimate_gas(&self, tx: &TransactionRequest) -> Result<U256> {
+        let tx: TypedTransaction = tx.clone().into();
+        let gas = self
+            .provider
+            .estimate_gas(&tx, None)
+            .await
+            .map_err(FrameError::from_revert)?;
+        Ok(gas)
+    }
+
+    /// Estimates the gas a transaction would have used against the state at a past block.
+    ///
+    /// This behaves like [`FrameClient::estimate_gas`]. Historical estimation usually requires
+    /// an archive node.
+    ///
+    /// # Errors
+    /// Returns `FrameError::ExecutionReverted` with the revert reason if the transaction would
+    /// revert at `block`, or another error if the provider cannot estimate it.
+    pub async fn estimate_gas_at_block(
+        &self,
+        tx: &TransactionRequest,
+        block: BlockId,
+    ) -> Result<U256> {
+        let tx: TypedTransaction = tx.clone().into();
+        let gas = self
+            .provider
+            .estimate_gas(&tx, Some(block))
+            .await
+            .map_err(FrameError::from_revert)?;
+        Ok(gas)
+    }
+
+    /// Asks the Frame wallet to sign an arbitrary message with `personal_sign` (EIP-191).
+    ///
+    /// The message is hex-encoded and sent together with the signing account to Frame, which
+    /// prompts the user and applies the `"\x19Ethereum Signed Message:\n"` prefix before signing.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` of the Frame account that should sign the message.
+    /// - `message`: The raw message bytes to sign. Strings can be passed directly.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `Signature` produced by Frame.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///
+    ///     let signature = client.sign_message(from, "hello frame").await?;
+    ///     assert_eq!(signature.recover("hello frame")?, from);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the Frame wallet is not accessible or the returned signature cannot be
+    /// parsed. If the user declines the request in Frame, the error is `FrameError::UserRejected`.
+    pub async fn sign_message(
+        &self,
+        from: Address,
+        message: impl AsRef<[u8]>,
+    ) -> Result<Signature> {
+        let signature = self.personal_sign(message.as_ref(), from).await?;
+        Ok(Signature::try_from(signature.as_ref())?.normalize_v())
+    }
+
+    /// Signs a batch of messages with `personal_sign`, one request at a time.
+    ///
+    /// Each message opens its own prompt in Frame once the previous one has been answered. If
+    /// the user rejects a message, the batch stops and `FrameError::BatchRejected` reports which
+    /// message was rejected along with the signatures collected so far. Use
+    /// [`FrameClient::sign_messages_with_policy`] to skip rejected messages instead.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` of the Frame account that should sign the messages.
+    /// - `messages`: The messages to sign.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps one `Signature` per message, in order.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::{client::FrameClient, error::FrameError};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///     let messages = vec![b"entry 1".to_vec(), b"entry 2".to_vec()];
+    ///
+    ///     match client.sign_messages(from, messages).await {
+    ///         Ok(signatures) => println!("Signed {} messages", signatures.len()),
+    ///         Err(FrameError::BatchRejected { index, signatures }) => {
+    ///             println!("Message {} was rejected after {} signatures", index, signatures.len());
+    ///         }
+    ///         Err(err) => return Err(err.into()),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `FrameError::BatchRejected` if the user rejects a message, or another error if
+    /// the Frame wallet is not accessible or a signature cannot be parsed.
+    pub async fn sign_messages(
+        &self,
+        from: Address,
+        messages: Vec<Vec<u8>>,
+    ) -> Result<Vec<Signature>> {
+        let signatures = self
+            .sign_messages_with_policy(from, messages, RejectionPolicy::Stop)
+            .await?;
+        Ok(signatures.into_iter().flatten().collect())
+    }
+
+    /// Signs a batch of messages with `personal_sign`, one request at a time, handling
+    /// rejections according to `policy`.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` of the Frame account that should sign the messages.
+    /// - `messages`: The messages to sign.
+    /// - `policy`: Whether to stop at the first rejected message or skip rejected messages.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps one entry per message, in order: the
+    /// `Signature`, or `None` if the message was rejected and skipped.
+    ///
+    /// # Errors
+    /// Returns `FrameError::BatchRejected` if the user rejects a message with
+    /// `RejectionPolicy::Stop`. Other errors, such as the Frame wallet not being accessible,
+    /// stop the batch with either policy.
+    pub async fn sign_messages_with_policy(
+        &self,
+        from: Address,
+        messages: Vec<Vec<u8>>,
+        policy: RejectionPolicy,
+    ) -> Result<Vec<Option<Signature>>> {
+        let mut signatures = Vec::with_capacity(messages.len());
+        for (index, message) in messages.iter().enumerate() {
+            match self.sign_message(from, message).await {
+                Ok(signature) => signatures.push(Some(signature)),
+                Err(FrameError::UserRejected) if policy == RejectionPolicy::Skip => {
+                    signatures.push(None)
+                }
+                Err(FrameError::UserRejected) => {
+                    return Err(FrameError::BatchRejected {
+                        index,
+                        signatures: signatures.into_iter().flatten().collect(),
+                    })
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(signatures)
+    }
+
+    /// Asks the Frame wallet to sign a message with `personal_sign` and returns the raw signature.
+    ///
+    /// `personal_sign` signs the EIP-191 digest of the message: Frame prepends the
+    /// `"\x19Ethereum Signed Message:\n" + len(message)` prefix before hashing, so the message is
+    /// sent unprefixed. Use [`FrameClient::sign_message`] to get a parsed `Signature` instead.
+    ///
+    /// # Parameters
+    /// - `message`: The raw message bytes to sign.
+    /// - `signer`: The `Address` of the Frame account that should sign the message.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the raw 65-byte signature as `Bytes`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let signer = client.get_accounts().await?[0];
+    ///
+    ///     let signature = client.personal_sign(b"hello frame", signer).await?;
+    ///     println!("Signature: {}", signature);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the Frame wallet is not accessible. If the user declines the request
+    /// in Frame, the error is `FrameError::UserRejected`.
+    pub async fn personal_sign(&self, message: &[u8], signer: Address) -> Result<Bytes> {
+        let message = Bytes::from(message.to_vec());
+        self.request("personal_sign", json!([message, signer]))
+            .await
+    }
+
+    /// Asks the Frame wallet to sign data with the legacy `eth_sign` method and returns the raw
+    /// signature.
+    ///
+    /// Unlike [`FrameClient::personal_sign`], the parameters are sent in `eth_sign` order
+    /// (`[signer, data]`) and the data is passed through as given.
+    ///
+    /// # Parameters
+    /// - `message`: The raw bytes to sign.
+    /// - `signer`: The `Address` of the Frame account that should sign the data.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the raw 65-byte signature as `Bytes`.
+    ///
+    /// # Errors
+    /// Returns an error if the Frame wallet is not accessible. If the user declines the request
+    /// in Frame, the error is `FrameError::UserRejected`.
+    pub async fn eth_sign(&self, message: &[u8], signer: Address) -> Result<Bytes> {
+        let message = Bytes::from(message.to_vec());
+        self.request("eth_sign", json!([signer, message])).await
+    }
+
+    /// Asks the Frame wallet to sign a raw 32-byte hash with `eth_sign`.
+    ///
+    /// **This is blind signing.** The user is shown an opaque hash instead of a readable message,
+    /// and the hash may be the signing hash of a transaction, permit or order that moves the
+    /// account's funds. Only use it for legacy verifiers that check `ecrecover(hash, v, r, s)`
+    /// without the EIP-191 prefix, and prefer [`FrameClient::sign_message`] or
+    /// [`FrameClient::sign_typed_data`] everywhere else. The method is disabled unless the client
+    /// was built with [`FrameClientBuilder::allow_unsafe_signing`].
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` of the Frame account that should sign the hash.
+    /// - `hash`: The 32-byte hash to sign, as is.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `Signature`, with `v` normalized to 27/28.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{H256, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::builder()
+    ///         .chain_id(U256::from(1))
+    ///         .allow_unsafe_signing(true)
+    ///         .build()
+    ///         .await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///     let hash: H256 = "0x...".parse()?;
+    ///
+    ///     let signature = client.sign_hash_unsafe(from, hash).await?;
+    ///     println!("Signature: {}", signature);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `FrameError::UnsafeSigningDisabled` without contacting Frame if unsafe signing
+    /// was not allowed, `FrameError::UserRejected` if the user declines the request and
+    /// `FrameError::BlindSigningRejected` if Frame refuses to blind-sign for the account.
+    pub async fn sign_hash_unsafe(&self, from: Address, hash: H256) -> Result<Signature> {
+        if !self.allow_unsafe_signing {
+            return Err(FrameError::UnsafeSigningDisabled);
+        }
+
+        let signature: Bytes = self
+            .request("eth_sign", json!([from, hash]))
+            .await
+            .map_err(|err| match err {
+                FrameError::RpcError { message, .. } => FrameError::BlindSigningRejected(message),
+                err => err,
+            })?;
+        Ok(Signature::try_from(signature.as_ref())?.normalize_v())
+    }
+
+    /// Asks the Frame wallet to sign an EIP-712 typed data document with `eth_signTypedData_v4`.
+    ///
+    /// The document is forwarded to Frame exactly as given: it is serialized once, preserving the
+    /// field order of `typed_data_json`, and sent as the JSON string parameter expected by
+    /// `eth_signTypedData_v4`. Integers that do not fit in a `u64` should be given as decimal or
+    /// hex strings, since `serde_json::Value` cannot represent them exactly.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` of the Frame account that should sign the typed data.
+    /// - `typed_data_json`: The full typed data document, with `types`, `primaryType`, `domain`
+    ///   and `message` members.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the 65-byte `Signature` produced by Frame.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    /// use serde_json::json;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///     let typed_data = json!({
+    ///         "types": {
+    ///             "EIP712Domain": [{ "name": "name", "type": "string" }],
+    ///             "Order": [{ "name": "amount", "type": "uint256" }]
+    ///         },
+    ///         "primaryType": "Order",
+    ///         "domain": { "name": "Exchange" },
+    ///         "message": { "amount": "1000000000000000000000000" }
+    ///     });
+    ///
+    ///     let signature = client.sign_typed_data_raw(from, typed_data).await?;
+    ///     println!("Signature: {}", signature);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the Frame wallet is not accessible or the returned signature cannot be
+    /// parsed. If the user declines the request in Frame, the error is `FrameError::UserRejected`;
+    /// if Frame rejects the typed data as malformed, `is_invalid_params()` returns `true`.
+    pub async fn sign_typed_data_raw(
+        &self,
+        from: Address,
+        typed_data_json: Value,
+    ) -> Result<Signature> {
+        let typed_data = serde_json::to_string(&typed_data_json)?;
+        let signature: Bytes = self
+            .request("eth_signTypedData_v4", json!([from, typed_data]))
+            .await?;
+        Ok(Signature::try_from(signature.as_ref())?.normalize_v())
+    }
+
+    /// Asks the Frame wallet to sign EIP-712 typed data with `eth_signTypedData_v4`.
+    ///
+    /// `payload` can be an ethers `TypedData` document or any struct that implements `Eip712`
+    /// and [`Eip712Types`](crate::eip712::Eip712Types), so structs that already implement
+    /// `Eip712` for contract interactions can be reused. Structs are converted into the typed
+    /// data wire format (`domain`, `types`, `primaryType` and `message`) and the conversion is
+    /// checked against their `encode_eip712()` before anything is sent to Frame.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` of the Frame account that should sign the typed data.
+    /// - `payload`: The typed data or struct to sign.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the 65-byte `Signature` produced by Frame.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{transaction::eip712::TypedData, U256};
+    /// use frame_rs::client::FrameClient;
+    /// use serde_json::json;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let owner = client.get_accounts().await?[0];
+    ///     let permit: TypedData = serde_json::from_value(json!({
+    ///         "types": {
+    ///             "Permit": [
+    ///                 { "name": "owner", "type": "address" },
+    ///                 { "name": "spender", "type": "address" },
+    ///                 { "name": "value", "type": "uint256" },
+    ///                 { "name": "nonce", "type": "uint256" },
+    ///                 { "name": "deadline", "type": "uint256" }
+    ///             ]
+    ///         },
+    ///         "primaryType": "Permit",
+    ///         "domain": {
+    ///             "name": "USD Coin",
+    ///             "version": "2",
+    ///             "chainId": 1,
+    ///             "verifyingContract": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"
+    ///         },
+    ///         "message": {
+    ///             "owner": owner,
+    ///             "spender": "0x000000000022D473030F116dDEE9F6B43aC78BA3",
+    ///             "value": "1000000",
+    ///             "nonce": "0",
+    ///             "deadline": "1893456000"
+    ///         }
+    ///     }))?;
+    ///
+    ///     let signature = client.sign_typed_data(owner, &permit).await?;
+    ///     println!("Permit signature: {}", signature);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if a struct cannot be converted into typed data that matches its
+    /// EIP-712 hash, or for any of the reasons listed on [`FrameClient::sign_typed_data_raw`],
+    /// including the user rejecting the request in Frame.
+    pub async fn sign_typed_data<T>(&self, from: Address, payload: &T) -> Result<Signature>
+    where
+        T: ToTypedData + Send + Sync + ?Sized,
+    {
+        let typed_data = payload.to_typed_data()?;
+        self.sign_typed_data_raw(from, serde_json::to_value(typed_data)?)
+            .await
+    }
+
+    /// Asks the Frame wallet to sign a transaction with `eth_signTransaction`, without
+    /// broadcasting it.
+    ///
+    /// Frame must be connected to the expected chain. The chain ID and nonce are filled in when
+    /// they are not set, using the expected chain ID, or the connected provider's if there is
+    /// none, and the pending nonce of `tx.from`. With nonce management enabled, the nonce is
+    /// reserved like for a sent transaction instead, and released again if Frame refuses to
+    /// sign. The transaction is never sent with
+    /// `eth_sendTransaction`; broadcasting the returned bytes is left to the caller, e.g. with
+    /// `eth_sendRawTransaction`.
+    ///
+    /// # Parameters
+    /// - `tx`: The `TransactionRequest` to sign. Its `from` address must be set to the Frame
+    ///   account that should sign it.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the RLP-encoded signed transaction.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::{
+    ///     middleware::Middleware,
+    ///     types::{Address, TransactionRequest, U256},
+    /// };
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let tx = TransactionRequest::new().from(from).to(to).value(U256::exp10(18));
+    ///     let raw = client.sign_transaction(tx).await?;
+    ///
+    ///     // Broadcast later, possibly through a different node.
+    ///     let tx_hash = client.send_raw_transaction(raw).await?;
+    ///     println!("Transaction hash: {:?}", tx_hash);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if `tx.from` is not set, `FrameError::PolicyViolation` if `tx` breaks
+    /// the client's policy, `FrameError::ChainMismatch` if Frame isn't connected to the expected
+    /// chain, and an error if the chain ID or nonce cannot be fetched, if Frame's answer holds
+    /// no signed transaction, or if the Frame wallet is not accessible. If the user declines the request in Frame, the
+    /// error is `FrameError::UserRejected`.
+    pub async fn sign_transaction(&self, tx: TransactionRequest) -> Result<Bytes> {
+        let Some(from) = tx.from else {
+            bail!("Cannot sign a transaction without a `from` address");
+        };
+        let mut typed = tx.into();
+        self.enforce_policy(&mut typed).await?;
+        let TypedTransaction::Legacy(mut tx) = typed else {
+            unreachable!("a TransactionRequest is a legacy transaction");
+        };
+        self.ensure_expected_chain().await?;
+        if tx.chain_id.is_none() {
+            let chain_id = match self.expected_chain_id().await {
+                Some(chain_id) => chain_id,
+                None => self.get_chain_id().await?,
+            };
+            let Ok(chain_id) = u64::try_from(chain_id) else {
+                bail!("Chain ID {} does not fit in 64 bits", chain_id);
+            };
+            tx.chain_id = Some(chain_id.into());
+        }
+        let reserved = match (&self.nonces, tx.nonce) {
+            (Some(nonces), None) => {
+                let (key, nonce) = self.reserve_nonce(nonces, from).await?;
+                tx.nonce = Some(nonce);
+                Some((nonces, key, nonce))
+            }
+            (None, None) => {
+                tx.nonce = Some(self.get_pending_nonce(from).await?);
+                None
+            }
+            _ => None,
+        };
+
+        // Frame returns the raw transaction, but signers modelled on Geth wrap it as `{ raw, tx }`.
+        let signed: Value = match self.request("eth_signTransaction", json!([tx])).await {
+            Ok(signed) => signed,
+            Err(err) => {
+                if let (Some((nonces, key, nonce)), true) = (reserved, err.is_refusal()) {
+                    nonces.release(key, nonce).await;
+                }
+                return Err(err);
+            }
+        };
+        let raw = match signed {
+            Value::Object(mut signed) => match signed.remove("raw") {
+                Some(raw) => raw,
+                None => bail!("Frame returned a signed transaction without `raw`"),
+            },
+            raw => raw,
+        };
+        Ok(serde_json::from_value(raw)?)
+    }
+
+    /// Broadcasts a signed transaction through a separate RPC endpoint instead of Frame.
+    ///
+    /// This pairs with [`FrameClient::sign_transaction`] to sign with Frame but submit through
+    /// a private relay or a node of your own. The hash returned by the endpoint is checked
+    /// against the hash of `signed`.
+    ///
+    /// # Parameters
+    /// - `signed`: The RLP-encoded signed transaction.
+    /// - `rpc_url`: The HTTP URL of the JSON-RPC endpoint to send `eth_sendRawTransaction` to.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` hash of the broadcast transaction.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, TransactionRequest, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let tx = TransactionRequest::new().from(from).to(to).value(U256::exp10(18));
+    ///     let signed = client.sign_transaction(tx).await?;
+    ///     let tx_hash = client
+    ///         .broadcast_via(signed, "https://rpc.flashbots.net")
+    ///         .await?;
+    ///     println!("Transaction hash: {:?}", tx_hash);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `FrameError::BroadcastFailed` if the endpoint cannot be reached, times out or
+    /// sends an invalid response, `FrameError::RpcError` if it rejects the transaction, and
+    /// `FrameError::HashMismatch` if it reports a different transaction hash.
+    pub async fn broadcast_via(&self, signed: Bytes, rpc_url: &str) -> Result<H256> {
+        let broadcast_failed = |message: String| FrameError::BroadcastFailed {
+            url: rpc_url.to_string(),
+            message,
+        };
+
+        let url = Url::parse(rpc_url).map_err(|err| broadcast_failed(err.to_string()))?;
+        let http = Http::new_with_client(url, self.rpc.client().clone());
+
+        let expected = H256::from(keccak256(&signed));
+        let actual: H256 = match http.request("eth_sendRawTransaction", [&signed]).await {
+            Ok(hash) => hash,
+            Err(HttpClientError::JsonRpcError(error)) => {
+                return Err(FrameError::from_rpc(error.code, error.message))
+            }
+            Err(err) => return Err(broadcast_failed(err.to_string())),
+        };
+
+        if actual != expected {
+            return Err(FrameError::HashMismatch { expected, actual });
+        }
+        Ok(actual)
+    }
+
+    /// Broadcasts a signed transaction with `eth_sendRawTransaction` on the connected provider.
+    ///
+    /// The transaction may have been signed by Frame with [`FrameClient::sign_transaction`] or
+    /// by any other signer, e.g. offline for relaying. No prompt is opened in Frame. Like
+    /// [`FrameClient::send_gas_token_pending`], this returns as soon as the node accepted the
+    /// transaction, without waiting for it to be mined.
+    ///
+    /// # Parameters
+    /// - `raw_tx`: The RLP-encoded signed transaction.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Bytes, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let raw: Bytes = "0x02f8...".parse()?;
+    ///
+    ///     let tx_hash = client.send_raw_transaction(raw).await?;
+    ///     println!("Transaction hash: {:?}", tx_hash);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the node rejects the transaction, e.g. because its nonce was already
+    /// used, or if the provider is not accessible.
+    pub async fn send_raw_transaction(&self, raw_tx: Bytes) -> Result<H256> {
+        let pending = self.provider.send_raw_transaction(raw_tx).await?;
+        Ok(pending.tx_hash())
+    }
+
+    /// RLP-encodes a transaction with its signature and broadcasts it like
+    /// [`FrameClient::send_raw_transaction`].
+    ///
+    /// # Parameters
+    /// - `tx`: The transaction that was signed.
+    /// - `signature`: The signature of `tx`.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Errors
+    /// Returns an error if the node rejects the transaction or if the provider is not
+    /// accessible.
+    pub async fn broadcast_signed(
+        &self,
+        tx: &TypedTransaction,
+        signature: &Signature,
+    ) -> Result<H256> {
+        self.send_raw_transaction(tx.rlp_signed(signature)).await
+    }
+
+    /// Sends a transaction through Frame and waits for its receipt, returning the transaction hash.
+    pub(crate) async fn send_and_wait<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+    ) -> Result<H256> {
+        let tx_receipt = self.send_and_wait_for_receipt(tx).await?;
+        Ok(tx_receipt.transaction_hash)
+    }
+
+    /// Sends a transaction through Frame and waits for its receipt.
+    pub(crate) async fn send_and_wait_for_receipt<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+    ) -> Result<TransactionReceipt> {
+        self.send_and_confirm(tx, 1, None).await
+    }
+
+    /// Sends a transaction through Frame and waits for `confirmations` blocks, giving up after
+    /// `timeout`, or the client's receipt timeout if it is `None`, like
+    /// [`FrameClient::confirm_sent`].
+    pub(crate) async fn send_and_confirm<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        confirmations: usize,
+        timeout: Option<Duration>,
+    ) -> Result<TransactionReceipt> {
+        self.confirm_sent(self.send_pending(tx), confirmations, timeout)
+            .await
+    }
+
+    /// Waits for the transaction submitted by `send` to get `confirmations` blocks, giving up
+    /// after `timeout`, or the client's receipt timeout if it is `None`.
+    ///
+    /// The deadline covers both the prompt in Frame and the wait for the receipt, so a timeout
+    /// carries the transaction hash only if the transaction was submitted.
+    pub(crate) async fn confirm_sent<'a>(
+        &'a self,
+        send: impl Future<Output = Result<PendingTx<'a, P>>>,
+        confirmations: usize,
+        timeout: Option<Duration>,
+    ) -> Result<TransactionReceipt> {
+        let Some(timeout) = timeout.or(self.receipt_timeout) else {
+            return send.await?.confirmations(confirmations).await;
+        };
+
+        let deadline = Instant::now() + timeout;
+        let pending_tx = tokio::time::timeout_at(deadline, send)
+            .await
+            .map_err(|_| FrameError::Timeout { tx_hash: None })??;
+        let tx_hash = pending_tx.hash();
+        tokio::time::timeout_at(deadline, pending_tx.confirmations(confirmations))
+            .await
+            .map_err(|_| FrameError::Timeout {
+                tx_hash: Some(tx_hash),
+            })?
+    }
+
+    /// Sends an arbitrary JSON-RPC request to Frame, for methods the client doesn't wrap.
+    ///
+    /// The request is posted to [`FrameClient::rpc_url`] in the standard JSON-RPC envelope and
+    /// waits in the prompt queue like the wrapped wallet requests, since the method may open a
+    /// prompt in Frame.
+    ///
+    /// # Parameters
+    /// - `method`: The JSON-RPC method, e.g. `wallet_getPermissions`.
+    /// - `params`: The `params` of the request, usually a JSON array.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `result` of the response deserialized
+    /// into `T`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    /// use serde_json::{json, Value};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let permissions: Value = client.raw_request("wallet_getPermissions", json!([])).await?;
+    ///     println!("{}", permissions);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `FrameError::UserRejected` if the user declines the request in Frame and
+    /// `FrameError::RpcError` for any other error response. Returns an error if Frame cannot be
+    /// reached or if the `result` doesn't deserialize into `T`.
+    pub async fn raw_request<T: DeserializeOwned + Send>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<T> {
+        self.request(method, params).await
+    }
+
+    /// Sends a JSON-RPC request directly to the Frame RPC endpoint and deserializes its `result`.
+    ///
+    /// Requests always go over HTTP, whatever the provider's transport. Frame reports most
+    /// failures with HTTP 200 and an `error` member in the response body, which is returned as
+    /// `FrameError::UserRejected` or `FrameError::RpcError`. Requests wait in the prompt queue
+    /// until no other prompt is open.
+    pub(crate) async fn request<T: DeserializeOwned + Send>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<T> {
+        let _prompt = self.lock_prompt().await?;
+        self.send_rpc(method, params).await
+    }
+
+    /// Waits until no other prompt is open in Frame, for at most the client's request timeout.
+    pub(crate) async fn lock_prompt(&self) -> Result<MutexGuard<'_, ()>> {
+        match self.request_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, self.prompt_queue.lock())
+                .await
+                .map_err(|_| FrameError::Timeout { tx_hash: None }),
+            None => Ok(self.prompt_queue.lock().await),
+        }
+    }
+
+    /// Sends a JSON-RPC request to the Frame RPC endpoint like [`FrameClient::request`], without
+    /// waiting in the prompt queue.
+    pub(crate) async fn send_rpc<T: DeserializeOwned + Send>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<T> {
+        match self.rpc.request(method, params).await {
+            Ok(result) => Ok(result),
+            Err(HttpClientError::JsonRpcError(error)) => {
+                Err(FrameError::from_rpc(error.code, error.message))
+            }
+            Err(HttpClientError::ReqwestError(err)) => Err(err.into()),
+            Err(err) => Err(FrameError::Other(err.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_balance() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push(U256::from(1_000)).unwrap();
+
+        let address = Address::random();
+        let balance = client.get_balance_latest(address).await.unwrap();
+        assert_eq!(balance, U256::from(1_000));
+        mock.assert_request("eth_getBalance", (address, "latest"))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_fills_chain_id_and_nonce() {
+        use ethers::{
+            signers::{LocalWallet, Signer},
+            types::Transaction,
+            utils::rlp::{Decodable, Rlp},
+        };
+
+        let wallet: LocalWallet =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                .parse()
+                .unwrap();
+        let tx = TransactionRequest::new()
+            .from(wallet.address())
+            .to(Address::random())
+            .value(U256::from(1))
+            .gas(21_000)
+            .gas_price(1_000_000_000);
+
+        let expected: TypedTransaction = tx.clone().chain_id(10).nonce(7).into();
+        let signature = wallet.sign_transaction_sync(&expected).unwrap();
+        let raw = expected.rlp_signed(&signature);
+
+        let (client, mock) =
+            FrameClient::mocked_with_rpc(vec![json!({ "jsonrpc": "2.0", "id": 1, "result": raw })])
+                .await;
+        mock.push(U256::from(7)).unwrap();
+        mock.push(U256::from(10)).unwrap();
+
+        let signed = client.sign_transaction(tx).await.unwrap();
+        assert_eq!(signed, raw);
+
+        let decoded = Transaction::decode(&Rlp::new(&signed)).unwrap();
+        assert_eq!(decoded.recover_from().unwrap(), wallet.address());
+        assert_eq!(decoded.nonce, U256::from(7));
+        assert_eq!(decoded.chain_id, Some(U256::from(10)));
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_checks_expected_chain() {
+        let (client, mock) = FrameClient::mocked_with_rpc(vec![]).await;
+        *client.expected_chain_id.write().await = Some(U256::from(10));
+        mock.push(U256::from(5)).unwrap();
+
+        let tx = TransactionRequest::new().from(Address::random()).nonce(7);
+        let err = client.sign_transaction(tx).await.unwrap_err();
+        assert!(matches!(
+            err,
+            FrameError::ChainMismatch { expected, actual }
+                if expected == U256::from(10) && actual == U256::from(5)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_rejected_releases_nonce() {
+        let (mut client, mock) = FrameClient::mocked_with_rpc(vec![json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": 4001, "message": "User rejected the request" }
+        })])
+        .await;
+        client.nonces = Some(NonceCache::default());
+        mock.push(U256::from(7)).unwrap();
+        mock.push(U256::one()).unwrap();
+
+        let from = Address::random();
+        let tx = TransactionRequest::new().from(from).chain_id(1);
+        let err = client.sign_transaction(tx).await.unwrap_err();
+        assert!(matches!(err, FrameError::UserRejected));
+
+        mock.push(U256::one()).unwrap();
+        let nonces = client.nonces.as_ref().unwrap();
+        let (_, nonce) = client.reserve_nonce(nonces, from).await.unwrap();
+        assert_eq!(nonce, U256::from(7));
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_without_raw() {
+        let (client, _) = FrameClient::mocked_with_rpc(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "result": { "tx": {} } }),
+        ])
+        .await;
+
+        let tx = TransactionRequest::new()
+            .from(Address::random())
+            .chain_id(10)
+            .nonce(7);
+        let err = client.sign_transaction(tx).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Frame returned a signed transaction without `raw`"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sign_hash_unsafe_requires_opt_in() {
+        let signature = Bytes::from([[1u8; 64].as_slice(), &[0]].concat());
+        let (mut client, _) = FrameClient::mocked_with_rpc(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "result": signature }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": -32000, "message": "Signer does not support eth_sign" }
+            }),
+        ])
+        .await;
+        let (from, hash) = (Address::random(), H256::random());
+
+        let err = client.sign_hash_unsafe(from, hash).await.unwrap_err();
+        assert!(matches!(err, FrameError::UnsafeSigningDisabled));
+
+        client.allow_unsafe_signing = true;
+        let signed = client.sign_hash_unsafe(from, hash).await.unwrap();
+        assert_eq!(signed.v, 27);
+
+        let err = client.sign_hash_unsafe(from, hash).await.unwrap_err();
+        assert!(matches!(err, FrameError::BlindSigningRejected(message)
+            if message == "Signer does not support eth_sign"));
+    }
+
+    #[tokio::test]
+    async fn test_sign_messages_rejection_policies() {
+        let signature = Bytes::from([[1u8; 64].as_slice(), &[27]].concat());
+        let signed = json!({ "jsonrpc": "2.0", "id": 1, "result": signature });
+        let rejected = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": 4001, "message": "User rejected the request" }
+        });
+        let (client, _) = FrameClient::mocked_with_rpc(vec![
+            signed.clone(),
+            rejected.clone(),
+            signed.clone(),
+            signed,
+            rejected,
+        ])
+        .await;
+        let from = Address::random();
+        let messages = vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()];
+
+        let skipped = client
+            .sign_messages_with_policy(from, messages.clone(), RejectionPolicy::Skip)
+            .await
+            .unwrap();
+        assert_eq!(
+            skipped.iter().map(Option::is_some).collect::<Vec<_>>(),
+            [true, false, true]
+        );
+
+        let stopped = client.sign_messages(from, messages).await.unwrap_err();
+        assert!(matches!(
+            stopped,
+            FrameError::BatchRejected { index: 1, signatures } if signatures.len() == 1
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_send_gas_token_with_nonce() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let tx_hash = H256::random();
+        push_mined(&mock, tx_hash);
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+
+        let sent = client
+            .send_gas_token_with_nonce(from, to, U256::from(1), Some(U256::from(7)))
+            .await
+            .unwrap();
+        assert_eq!(sent, tx_hash);
+
+        let estimated: TypedTransaction = TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .value(1)
+            .nonce(7)
+            .gas_price(1)
+            .into();
+        let mut sent = estimated.clone();
+        sent.set_gas(21_000);
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+        mock.assert_request("eth_estimateGas", [estimated]).unwrap();
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_raw() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let tx_hash = H256::random();
+        push_mined(&mock, tx_hash);
+        mock.push(U256::from(50_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+
+        let data = Bytes::from(vec![0x3a, 0x4b, 0x66, 0xf1]);
+        let sent = client
+            .send_transaction_raw(from, to, data.clone(), Some(U256::from(5)))
+            .await
+            .unwrap();
+        assert_eq!(sent, tx_hash);
+
+        let estimated: TypedTransaction = TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .data(data)
+            .value(5)
+            .gas_price(1)
+            .into();
+        let mut sent = estimated.clone();
+        sent.set_gas(50_000);
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+        mock.assert_request("eth_estimateGas", [estimated]).unwrap();
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_fills_from() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let tx_hash = H256::random();
+        push_mined(&mock, tx_hash);
+        mock.push::<Vec<Address>, _>(vec![from]).unwrap();
+
+        let tx = TransactionRequest::new()
+            .to(to)
+            .data(vec![0xd0, 0xe3, 0x0d, 0xb0])
+            .gas(60_000)
+            .gas_price(2)
+            .nonce(3);
+        let sent = client.send_transaction(tx.clone()).await.unwrap();
+        assert_eq!(sent, tx_hash);
+
+        let sent: TypedTransaction = tx.from(from).into();
+        mock.assert_request("eth_accounts", ()).unwrap();
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_gas_token_eip1559_estimates_fees() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let tx_hash = H256::random();
+        push_mined(&mock, tx_hash);
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push(U256::from(2)).unwrap();
+        mock.push(ethers::types::Block::<H256> {
+            base_fee_per_gas: Some(U256::from(10)),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let sent = client
+            .send_gas_token_eip1559(from, to, U256::from(1), None)
+            .await
+            .unwrap();
+        assert_eq!(sent, tx_hash);
+
+        let estimated: TypedTransaction = ethers::types::Eip1559TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .value(1)
+            .max_fee_per_gas(22)
+            .max_priority_fee_per_gas(2)
+            .into();
+        mock.assert_request("eth_getBlockByNumber", ("latest", false))
+            .unwrap();
+        mock.assert_request("eth_maxPriorityFeePerGas", ()).unwrap();
+        mock.assert_request("eth_estimateGas", [estimated]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_gas_token_eip1559_falls_back_to_legacy() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let tx_hash = H256::random();
+        push_mined(&mock, tx_hash);
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        mock.push(ethers::types::Block::<H256>::default()).unwrap();
+
+        let sent = client
+            .send_gas_token_eip1559(from, to, U256::from(1), None)
+            .await
+            .unwrap();
+        assert_eq!(sent, tx_hash);
+
+        mock.assert_request("eth_getBlockByNumber", ("latest", false))
+            .unwrap();
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_gas_token_eip1559_legacy_keeps_max_fee() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let tx_hash = H256::random();
+        push_mined(&mock, tx_hash);
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push(ethers::types::Block::<H256>::default()).unwrap();
+
+        let fees = FeeOverrides {
+            max_fee_per_gas: U256::from(30),
+            max_priority_fee_per_gas: U256::from(2),
+        };
+        let sent = client
+            .send_gas_token_eip1559(from, to, U256::from(1), Some(fees))
+            .await
+            .unwrap();
+        assert_eq!(sent, tx_hash);
+
+        let estimated: TypedTransaction = TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .value(1)
+            .gas_price(30)
+            .into();
+        mock.assert_request("eth_getBlockByNumber", ("latest", false))
+            .unwrap();
+        mock.assert_request("eth_estimateGas", [estimated]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_gas_token_with_opts_legacy() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let tx_hash = H256::random();
+        push_mined(&mock, tx_hash);
+
+        let opts = SendOptions {
+            gas_limit: Some(U256::from(30_000)),
+            nonce: Some(U256::from(12)),
+            gas_price: Some(U256::from(5)),
+            ..Default::default()
+        };
+        let sent = client
+            .send_gas_token_with_opts(from, to, U256::from(1), opts)
+            .await
+            .unwrap();
+        assert_eq!(sent, tx_hash);
+
+        let expected: TypedTransaction = TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .value(1)
+            .gas(30_000)
+            .nonce(12)
+            .gas_price(5)
+            .into();
+        mock.assert_request("eth_sendTransaction", [expected])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_gas_token_with_opts_eip1559() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let tx_hash = H256::random();
+        push_mined(&mock, tx_hash);
+
+        let opts = SendOptions {
+            gas_limit: Some(U256::from(21_000)),
+            nonce: Some(U256::from(3)),
+            max_fee_per_gas: Some(U256::from(40)),
+            max_priority_fee_per_gas: Some(U256::from(2)),
+            ..Default::default()
+        };
+        client
+            .send_gas_token_with_opts(from, to, U256::from(1), opts)
+            .await
+            .unwrap();
+
+        let expected: TypedTransaction = ethers::types::Eip1559TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .value(1)
+            .gas(21_000)
+            .nonce(3)
+            .max_fee_per_gas(40)
+            .max_priority_fee_per_gas(2)
+            .into();
+        mock.assert_request("eth_sendTransaction", [expected])
+            .unwrap();
+
+        let conflicting = SendOptions {
+            gas_price: Some(U256::from(5)),
+            ..opts
+        };
+        assert!(client
+            .send_gas_token_with_opts(from, to, U256::from(1), conflicting)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_gas_token_receipt_timeout() {
+        let (mut client, mock) = FrameClient::mocked();
+        client.receipt_timeout = Some(Duration::from_millis(50));
+        // No receipt is ever returned: polling for it fails until the deadline.
+        let tx_hash = H256::random();
+        mock.push(tx_hash).unwrap();
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+
+        let err = client
+            .send_gas_token(Address::random(), Address::random(), U256::from(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FrameError::Timeout { tx_hash: Some(h) } if h == tx_hash));
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_in_prompt_queue() {
+        let (mut client, _) = FrameClient::mocked();
+        client.request_timeout = Some(Duration::from_millis(20));
+        // A prompt the user walked away from keeps the queue busy.
+        let _prompt = client.prompt_queue.lock().await;
+
+        let err = client
+            .sign_message(Address::random(), b"hello")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FrameError::Timeout { tx_hash: None }));
+        let err = client
+            .send_gas_token_pending(Address::random(), Address::random(), U256::from(1))
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, FrameError::Timeout { tx_hash: None }));
+    }
+
+    #[tokio::test]
+    async fn test_send_timeout_before_submission() {
+        let (client, _) = FrameClient::mocked();
+        // Another prompt stays open in Frame, so the transaction is never submitted.
+        let _prompt = client.prompt_queue.lock().await;
+
+        let err = client
+            .send_gas_token_confirmed(
+                Address::random(),
+                Address::random(),
+                U256::from(1),
+                1,
+                Some(Duration::from_millis(20)),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FrameError::Timeout { tx_hash: None }));
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_signed() {
+        use ethers::{
+            core::rand::thread_rng,
+            signers::{LocalWallet, Signer},
+        };
+
+        let (client, mock) = FrameClient::mocked();
+        let wallet = LocalWallet::new(&mut thread_rng()).with_chain_id(1u64);
+        let tx: TypedTransaction = TransactionRequest::new()
+            .from(wallet.address())
+            .to(Address::random())
+            .value(1)
+            .nonce(0)
+            .gas(21_000)
+            .gas_price(1)
+            .chain_id(1)
+            .into();
+        let signature = wallet.sign_transaction_sync(&tx).unwrap();
+        let raw = tx.rlp_signed(&signature);
+        let tx_hash = H256::from(keccak256(&raw));
+        mock.push(tx_hash).unwrap();
+
+        assert_eq!(
+            client.broadcast_signed(&tx, &signature).await.unwrap(),
+            tx_hash
+        );
+        mock.assert_request("eth_sendRawTransaction", [raw])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_pending_nonce() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push(U256::from(3)).unwrap();
+
+        let address = Address::random();
+        assert_eq!(
+            client.get_pending_nonce(address).await.unwrap(),
+            U256::from(3)
+        );
+        mock.assert_request("eth_getTransactionCount", (address, "pending"))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_switch_network_sets_expected_chain() {
+        let (client, _) = FrameClient::mocked_with_rpc(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "result": null }),
+        ])
+        .await;
+        assert_eq!(client.expected_chain_id().await, None);
+
+        client.switch_network(U256::from(10)).await.unwrap();
+        assert_eq!(client.expected_chain_id().await, Some(U256::from(10)));
+    }
+
+    #[tokio::test]
+    async fn test_request_accounts() {
+        let account = Address::random();
+        let (client, _) = FrameClient::mocked_with_rpc(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "result": [account] }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": 4001, "message": "User rejected the request." }
+            }),
+        ])
+        .await;
+
+        assert_eq!(client.request_accounts().await.unwrap(), vec![account]);
+        let err = client.request_accounts().await.unwrap_err();
+        assert!(matches!(err, FrameError::UserRejected));
+    }
+
+    #[tokio::test]
+    async fn test_raw_request() {
+        let (client, _) = FrameClient::mocked_with_rpc(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "result": "0x2a" }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": -32601, "message": "method not found" }
+            }),
+        ])
+        .await;
+
+        let result: U256 = client.raw_request("eth_chainId", json!([])).await.unwrap();
+        assert_eq!(result, U256::from(42));
+        let err = client
+            .raw_request::<Value>("frame_unknown", json!([]))
+            .await
+            .unwrap_err();
+        assert!(err.is_method_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_get_confirmed_nonce() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push(U256::from(2)).unwrap();
+
+        let address = Address::random();
+        assert_eq!(
+            client.get_confirmed_nonce(address).await.unwrap(),
+            U256::from(2)
+        );
+        mock.assert_request("eth_getTransactionCount", (address, "latest"))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_via_checks_hash() {
+        let signed = Bytes::from(vec![0xf8, 0x6b, 0x01]);
+        let expected = H256::from(keccak256(&signed));
+        let (client, _) = FrameClient::mocked_with_rpc(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "result": expected }),
+            json!({ "jsonrpc": "2.0", "id": 1, "result": H256::zero() }),
+        ])
+        .await;
+        let rpc_url = client.rpc_url.clone();
+
+        let tx_hash = client.broadcast_via(signed.clone(), &rpc_url).await;
+        assert_eq!(tx_hash.unwrap(), expected);
+
+        let mismatch = client.broadcast_via(signed.clone(), &rpc_url).await;
+        assert!(matches!(
+            mismatch,
+            Err(FrameError::HashMismatch { actual, .. }) if actual == H256::zero()
+        ));
+
+        let unreachable = client.broadcast_via(signed, "http://127.0.0.1:1").await;
+        assert!(matches!(
+            unreachable,
+            Err(FrameError::BroadcastFailed { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_gas() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push(U256::from(21_000)).unwrap();
+
+        let tx = TransactionRequest::new()
+            .to(Address::random())
+            .value(U256::from(1));
+        assert_eq!(client.estimate_gas(&tx).await.unwrap(), U256::from(21_000));
+    }
+
+    #[tokio::test]
+    async fn test_call_surfaces_revert_data() {
+        use ethers::providers::{JsonRpcError, MockResponse};
+
+        let (client, mock) = FrameClient::mocked();
+        // A custom error, `InsufficientBalance()`, has no reason to decode.
+        let data = Bytes::from(ethers::utils::id("InsufficientBalance()").to_vec());
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_string(),
+            data: Some(json!(data)),
+        }));
+
+        let err = client
+            .call(&TransactionRequest::new().to(Address::random()), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FrameError::ExecutionReverted { reason, data: Some(revert) }
+                if reason == "execution reverted" && revert == data
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_call_contract() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push::<Bytes, _>(Bytes::from(vec![0x2a])).unwrap();
+
+        let (to, from) = (Address::random(), Address::random());
+        let output = client
+            .call_contract(to, Bytes::from(vec![0x18, 0x16, 0x0d, 0xdd]), Some(from))
+            .await
+            .unwrap();
+        assert_eq!(output, Bytes::from(vec![0x2a]));
+
+        let tx: TypedTransaction = TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .data(vec![0x18, 0x16, 0x0d, 0xdd])
+            .into();
+        mock.assert_request("eth_call", (tx, "latest")).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_estimate_gas_decodes_revert_reason() {
+        use ethers::{
+            abi::Token,
+            providers::{JsonRpcError, MockResponse},
+        };
+
+        let (client, mock) = FrameClient::mocked();
+        let mut data = vec![0x08, 0xc3, 0x79, 0xa0];
+        data.extend(ethers::abi::encode(&[Token::String(
+            "ERC20: transfer amount exceeds balance".to_string(),
+        )]));
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_string(),
+            data: Some(json!(Bytes::from(data))),
+        }));
+
+        let err = client
+            .estimate_gas(&TransactionRequest::new().to(Address::random()))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FrameError::ExecutionReverted { reason, .. } if reason == "ERC20: transfer amount exceeds balance"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_switch_network_unknown_chain() {
+        let (client, _) = FrameClient::mocked_with_rpc(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "result": null }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": 4902, "message": "Unrecognized chain ID" }
+            }),
+        ])
+        .await;
+
+        client.switch_network(U256::from(10)).await.unwrap();
+        let err = client.switch_network(U256::from(8453)).await.unwrap_err();
+        assert!(matches!(err, FrameError::ChainNotFound(id) if id == U256::from(8453)));
+    }
+
+    #[tokio::test]
+    async fn test_switch_network_user_rejected() {
+        let (client, _) = FrameClient::mocked_with_rpc(vec![json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": 4001, "message": "User rejected the request" }
+        })])
+        .await;
+
+        let err = client.switch_network(U256::from(10)).await.unwrap_err();
+        assert!(err.is_user_rejected());
+    }
+
+    #[tokio::test]
+    async fn test_switch_network_error_body() {
+        // Frame answers with HTTP 200 and reports the failure in the `error` member.
+        let (client, _) = FrameClient::mocked_with_rpc(vec![json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": -32000, "message": "Chain is disabled" }
+        })])
+        .await;
+
+        let err = client.switch_network(U256::from(10)).await.unwrap_err();
+        assert!(matches!(err, FrameError::RpcError { code: -32000, message }
+            if message == "Chain is disabled"));
+    }
+
+    #[tokio::test]
+    async fn test_ensure_network() {
+        let (client, _) = FrameClient::mocked_with_rpc(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "result": null }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": 4902, "message": "Unrecognized chain ID" }
+            }),
+            json!({ "jsonrpc": "2.0", "id": 1, "result": null }),
+            json!({ "jsonrpc": "2.0", "id": 1, "result": null }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": 4001, "message": "User rejected the request" }
+            }),
+        ])
+        .await;
+        let config = ChainConfig {
+            chain_id: U256::from(8453),
+            chain_name: "Base".to_string(),
+            native_currency: crate::types::NativeCurrency {
+                name: "Ether".to_string(),
+                symbol: "ETH".to_string(),
+                decimals: 18,
+            },
+            rpc_urls: vec!["https://mainnet.base.org".to_string()],
+            block_explorer_urls: None,
+            icon_urls: None,
+        };
+
+        // A known chain is only switched to; an unknown one is added first.
+        client.ensure_network(&config).await.unwrap();
+        client.ensure_network(&config).await.unwrap();
+        assert!(matches!(
+            client.switch_network(config.chain_id).await,
+            Err(FrameError::UserRejected)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_watch_asset() {
+        let url = serve_http(vec![
+            (
+                200,
+                json!({ "jsonrpc": "2.0", "id": 1, "result": true }).to_string(),
+            ),
+            (
+                200,
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "error": { "code": 4001, "message": "User rejected the request" }
+                })
+                .to_string(),
+            ),
+        ])
+        .await;
+        let (mut client, _) = FrameClient::mocked();
+        client.rpc = FrameTransport::new(url.parse().unwrap(), Default::default());
+        let options = WatchAssetOptions {
+            address: Address::random(),
+            symbol: "TKN".to_string(),
+            decimals: 18,
+            image: None,
+        };
+
+        assert!(client
+            .watch_asset(AssetType::Erc20, options.clone())
+            .await
+            .unwrap());
+        assert!(!client.watch_asset(AssetType::Erc20, options).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_ping() {
+        let (client, _) = FrameClient::mocked_with_rpc(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "result": "1" }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "error": { "code": -32000, "message": "No connection to node" }
+            }),
+        ])
+        .await;
+        // Holding the prompt queue doesn't delay a ping.
+        let _prompt = client.prompt_queue.lock().await;
+        let latency = client.ping().await.unwrap();
+        assert!(latency < PING_TIMEOUT);
+        assert!(client.is_connected().await);
+
+        let listener = std::net::TcpListener::bind((DEFAULT_HOST, 0)).unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let (mut client, _) = FrameClient::mocked();
+        client.rpc = FrameTransport::new(url.parse().unwrap(), Default::default());
+        assert!(matches!(
+            client.ping().await,
+            Err(FrameError::ConnectionFailed(_))
+        ));
+        assert!(!client.is_connected().await);
+    }
+
+    #[tokio::test]
+    async fn test_permissions() {
+        let permission = json!({
+            "caveats": [{
+                "type": "restrictReturnedAccounts",
+                "value": ["0x8ba1f109551bd432803012645ac136ddd64dba72"]
+            }],
+            "date": 1700000000000u64,
+            "id": "3f6e1d4c-9a1b-4c6e-8f1e-2b7d9c0a5e11",
+            "invoker": "http://localhost:3000",
+            "parentCapability": "eth_accounts"
+        });
+        let (client, _) = FrameClient::mocked_with_rpc(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "result": [permission] }),
+            json!({ "jsonrpc": "2.0", "id": 2, "result": [permission] }),
+            json!({ "jsonrpc": "2.0", "id": 3, "result": null }),
+        ])
+        .await;
+
+        let granted = client
+            .request_permissions(&[PermissionRequest::new("eth_accounts")])
+            .await
+            .unwrap();
+        assert_eq!(granted.len(), 1);
+        assert_eq!(granted[0].parent_capability, "eth_accounts");
+        assert_eq!(granted[0].date, 1700000000000);
+        assert_eq!(
+            granted[0].caveats[0].caveat_type,
+            "restrictReturnedAccounts"
+        );
+        assert_eq!(client.get_permissions().await.unwrap(), granted);
+        client.revoke_permissions("eth_accounts").await.unwrap();
+    }
+
+    #[test]
+    fn test_watch_asset_params() {
+        let address = Address::random();
+        let options = WatchAssetOptions {
+            address,
+            symbol: "TKN".to_string(),
+            decimals: 6,
+            image: Some("https://example.com/tkn.png".to_string()),
+        };
+        assert_eq!(
+            json!({ "type": AssetType::Erc20, "options": options }),
+            json!({
+                "type": "ERC20",
+                "options": {
+                    "address": address,
+                    "symbol": "TKN",
+                    "decimals": 6,
+                    "image": "https://example.com/tkn.png"
+                }
+            })
+        );
+    }
 
     #[tokio::test]
     async fn test_switch_network() {
-        let client = FrameClient::new(U256::from(1), None).await.unwrap();
+        let client = FrameClient::new(U256::from(1), None, None).await.unwrap();
         assert_eq!(client.get_chain_id().await.unwrap(), U256::from(1));
         let next_chain_id = U256::from(42161);
         client.switch_network(next_chain_id).await.unwrap();
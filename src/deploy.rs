@@ -0,0 +1,193 @@
+use crate::{
+    client::FrameClient,
+    error::{bail, Result},
+};
+use ethers::{
+    providers::JsonRpcClient,
+    types::{Address, Bytes, TransactionRequest, H256, U256},
+    utils::get_contract_address,
+};
+
+/// Computes the address of the contract `deployer` creates with the transaction of nonce
+/// `nonce`: the last 20 bytes of the keccak256 hash of the RLP-encoded `[deployer, nonce]`.
+///
+/// Combined with [`FrameClient::get_pending_nonce`], this predicts the address of a
+/// deployment before it is sent.
+///
+/// # Examples
+/// ```
+/// use ethers::types::{Address, U256};
+/// use frame_rs::deploy::compute_contract_address;
+///
+/// let deployer: Address = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0".parse().unwrap();
+/// let address = compute_contract_address(deployer, U256::zero());
+/// assert_eq!(
+///     address,
+///     "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d".parse::<Address>().unwrap()
+/// );
+/// ```
+pub fn compute_contract_address(deployer: Address, nonce: U256) -> Address {
+    get_contract_address(deployer, nonce)
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Deploys a contract through Frame and waits for the deployment to be mined.
+    ///
+    /// The transaction has no recipient and its data is `bytecode` followed by the ABI-encoded
+    /// `constructor_args`.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` that signs and sends the deployment.
+    /// - `bytecode`: The creation bytecode of the contract.
+    /// - `constructor_args`: The ABI-encoded constructor arguments, empty if there are none.
+    /// - `value`: The amount of the gas token to send to a payable constructor, in Wei. `None`
+    ///   sends no value.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `Address` of the deployed contract and the
+    /// `H256` transaction hash.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::{
+    ///     abi::{encode, Token},
+    ///     types::{Bytes, U256},
+    /// };
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(11155111), None, None).await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///     let bytecode: Bytes = "0x...".parse()?;
+    ///     let args = encode(&[Token::Uint(U256::from(1_000_000))]);
+    ///
+    ///     let (address, tx_hash) = client
+    ///         .deploy_contract(from, bytecode, args.into(), None)
+    ///         .await?;
+    ///     println!("Deployed at {:?} in {:?}", address, tx_hash);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the deployment fails to be sent, if no receipt is returned for it or
+    /// if the receipt has no contract address. A reverted deployment is returned as
+    /// `FrameError::TransactionReverted`. If the user declines the transaction in Frame, the
+    /// error is `FrameError::UserRejected`.
+    pub async fn deploy_contract(
+        &self,
+        from: Address,
+        bytecode: Bytes,
+        constructor_args: Bytes,
+        value: Option<U256>,
+    ) -> Result<(Address, H256)> {
+        let data = [bytecode.as_ref(), constructor_args.as_ref()].concat();
+        let mut tx = TransactionRequest::new().from(from).data(data);
+        tx.value = value;
+
+        let receipt = self.send_and_wait_for_receipt(tx).await?;
+        let Some(address) = receipt.contract_address else {
+            bail!(
+                "Deployment {:?} has no contract address",
+                receipt.transaction_hash
+            );
+        };
+        Ok((address, receipt.transaction_hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::push_receipt;
+    use ethers::types::{transaction::eip2718::TypedTransaction, TransactionReceipt};
+
+    fn mined(mock: &ethers::providers::MockProvider, receipt: TransactionReceipt) {
+        let tx_hash = receipt.transaction_hash;
+        push_receipt(mock, receipt);
+        mock.push(tx_hash).unwrap();
+        mock.push(U256::from(200_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_deploy_contract() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, contract) = (Address::random(), Address::random());
+        let tx_hash = H256::random();
+        mined(
+            &mock,
+            TransactionReceipt {
+                transaction_hash: tx_hash,
+                block_number: Some(1.into()),
+                contract_address: Some(contract),
+                ..Default::default()
+            },
+        );
+
+        let deployed = client
+            .deploy_contract(
+                from,
+                Bytes::from_static(&[0x60, 0x80]),
+                Bytes::from_static(&[0x01]),
+                Some(U256::from(5)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(deployed, (contract, tx_hash));
+
+        let estimated: TypedTransaction = TransactionRequest::new()
+            .from(from)
+            .data(vec![0x60, 0x80, 0x01])
+            .value(5)
+            .gas_price(1)
+            .into();
+        let mut sent = estimated.clone();
+        sent.set_gas(200_000);
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+        mock.assert_request("eth_estimateGas", [estimated]).unwrap();
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+    }
+
+    #[test]
+    fn test_compute_contract_address() {
+        let deployer: Address = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0"
+            .parse()
+            .unwrap();
+        let expected: [Address; 2] = [
+            "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"
+                .parse()
+                .unwrap(),
+            "0x343c43a37d37dff08ae8c4a11544c718abb4fcf8"
+                .parse()
+                .unwrap(),
+        ];
+        for (nonce, expected) in expected.into_iter().enumerate() {
+            assert_eq!(compute_contract_address(deployer, nonce.into()), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deploy_contract_without_address() {
+        let (client, mock) = FrameClient::mocked();
+        let tx_hash = H256::random();
+        mined(
+            &mock,
+            TransactionReceipt {
+                transaction_hash: tx_hash,
+                block_number: Some(1.into()),
+                ..Default::default()
+            },
+        );
+
+        let err = client
+            .deploy_contract(Address::random(), Bytes::new(), Bytes::new(), None)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Deployment {:?} has no contract address", tx_hash)
+        );
+    }
+}
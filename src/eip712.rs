@@ -0,0 +1,393 @@
+use crate::error::{bail, ensure, Result};
+use anyhow::anyhow;
+use ethers::types::{
+    transaction::eip712::{EIP712Domain, Eip712, Eip712DomainType, TypedData, Types},
+    H256,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Describes the EIP-712 type layout of a struct so it can be sent to Frame as typed data.
+///
+/// `Eip712` only exposes hashes, while `eth_signTypedData_v4` needs the full type definitions.
+/// Implementing this trait next to an existing `Eip712` implementation lets the struct be
+/// converted with [`ToTypedData`] and signed with [`FrameClient::sign_typed_data`](crate::client::FrameClient::sign_typed_data).
+pub trait Eip712Types {
+    /// The name of the struct's EIP-712 type, e.g. `Mail`.
+    fn primary_type(&self) -> String;
+
+    /// The definitions of the primary type and every struct type it references, not including
+    /// `EIP712Domain`, which is derived from the struct's domain.
+    fn types(&self) -> Types;
+}
+
+/// Types that can be converted into the `eth_signTypedData_v4` wire format (`domain`, `types`,
+/// `primaryType` and `message`).
+///
+/// This is implemented for ethers' `TypedData` and for every serializable struct that
+/// implements both `Eip712` and [`Eip712Types`].
+pub trait ToTypedData {
+    /// Converts `self` into `TypedData`, adding the `EIP712Domain` type definition if it is
+    /// missing.
+    fn to_typed_data(&self) -> Result<TypedData>;
+}
+
+impl ToTypedData for TypedData {
+    fn to_typed_data(&self) -> Result<TypedData> {
+        let mut typed_data = self.clone();
+        typed_data
+            .types
+            .entry("EIP712Domain".to_string())
+            .or_insert_with(|| domain_types(&self.domain));
+        Ok(typed_data)
+    }
+}
+
+/// The message is produced by serializing the struct with serde, so its field names must match
+/// the names used in [`Eip712Types::types`] (typically via `#[serde(rename_all = "camelCase")]`).
+///
+/// # Errors
+/// Returns an error if the struct does not serialize to a JSON object, or if the EIP-712 hash of
+/// the produced typed data does not match `encode_eip712()`, which means the type definitions
+/// or the serialized message disagree with the struct's `Eip712` implementation.
+impl<T> ToTypedData for T
+where
+    T: Eip712 + Eip712Types + Serialize,
+{
+    fn to_typed_data(&self) -> Result<TypedData> {
+        let domain = self.domain().map_err(|err| anyhow!("{}", err))?;
+        let mut types = self.types();
+        types.insert("EIP712Domain".to_string(), domain_types(&domain));
+
+        let message = match serde_json::to_value(self)? {
+            Value::Object(message) => message.into_iter().collect(),
+            other => bail!(
+                "EIP-712 message must serialize to a JSON object, got {}",
+                other
+            ),
+        };
+
+        let typed_data = TypedData {
+            domain,
+            types,
+            primary_type: self.primary_type(),
+            message,
+        };
+
+        let expected = self.encode_eip712().map_err(|err| anyhow!("{}", err))?;
+        ensure!(
+            typed_data.encode_eip712()? == expected,
+            "typed data for {} does not match its EIP-712 hash",
+            typed_data.primary_type
+        );
+
+        Ok(typed_data)
+    }
+}
+
+/// Returns the EIP-712 domain separator (`hashStruct(domain)`) of `payload`.
+///
+/// # Errors
+/// Returns an error if `payload` cannot be converted into typed data.
+pub fn domain_hash<T: ToTypedData + ?Sized>(payload: &T) -> Result<H256> {
+    Ok(payload.to_typed_data()?.domain.separator().into())
+}
+
+/// Returns `hashStruct(message)` of `payload` for its primary type.
+///
+/// # Errors
+/// Returns an error if `payload` cannot be converted into typed data or the message does not
+/// match its type definitions.
+pub fn struct_hash<T: ToTypedData + ?Sized>(payload: &T) -> Result<H256> {
+    Ok(payload.to_typed_data()?.struct_hash()?.into())
+}
+
+/// Returns the EIP-712 digest of `payload`, i.e. the hash that Frame signs for
+/// `eth_signTypedData_v4`. Recovering a signature against this hash yields the signer.
+///
+/// # Errors
+/// Returns an error if `payload` cannot be converted into typed data or the message does not
+/// match its type definitions.
+pub fn typed_data_hash<T: ToTypedData + ?Sized>(payload: &T) -> Result<H256> {
+    Ok(payload.to_typed_data()?.encode_eip712()?.into())
+}
+
+/// Returns the `EIP712Domain` type definition for the fields that are set on `domain`.
+fn domain_types(domain: &EIP712Domain) -> Vec<Eip712DomainType> {
+    let fields = [
+        ("name", "string", domain.name.is_some()),
+        ("version", "string", domain.version.is_some()),
+        ("chainId", "uint256", domain.chain_id.is_some()),
+        (
+            "verifyingContract",
+            "address",
+            domain.verifying_contract.is_some(),
+        ),
+        ("salt", "bytes32", domain.salt.is_some()),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, _, present)| *present)
+        .map(|(name, r#type, _)| Eip712DomainType {
+            name: name.to_string(),
+            r#type: r#type.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        abi::{encode, Token},
+        types::{Address, U256},
+        utils::{hex, keccak256},
+    };
+
+    fn field(name: &str, r#type: &str) -> Eip712DomainType {
+        Eip712DomainType {
+            name: name.to_string(),
+            r#type: r#type.to_string(),
+        }
+    }
+
+    fn mail_domain() -> EIP712Domain {
+        EIP712Domain {
+            name: Some("Ether Mail".to_string()),
+            version: Some("1".to_string()),
+            chain_id: Some(U256::from(1)),
+            verifying_contract: Some(
+                "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+                    .parse()
+                    .unwrap(),
+            ),
+            salt: None,
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Person {
+        name: String,
+        wallet: Address,
+    }
+
+    impl Person {
+        fn struct_hash(&self) -> [u8; 32] {
+            let type_hash = keccak256("Person(string name,address wallet)");
+            keccak256(encode(&[
+                Token::FixedBytes(type_hash.to_vec()),
+                Token::FixedBytes(keccak256(&self.name).to_vec()),
+                Token::Address(self.wallet),
+            ]))
+        }
+    }
+
+    /// The `Mail` example from the EIP-712 specification, with a nested struct type.
+    #[derive(Serialize)]
+    struct Mail {
+        from: Person,
+        to: Person,
+        contents: String,
+    }
+
+    impl Eip712 for Mail {
+        type Error = std::convert::Infallible;
+
+        fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+            Ok(mail_domain())
+        }
+
+        fn type_hash() -> Result<[u8; 32], Self::Error> {
+            Ok(keccak256(
+                "Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+            ))
+        }
+
+        fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+            Ok(keccak256(encode(&[
+                Token::FixedBytes(Self::type_hash()?.to_vec()),
+                Token::FixedBytes(self.from.struct_hash().to_vec()),
+                Token::FixedBytes(self.to.struct_hash().to_vec()),
+                Token::FixedBytes(keccak256(&self.contents).to_vec()),
+            ])))
+        }
+    }
+
+    impl Eip712Types for Mail {
+        fn primary_type(&self) -> String {
+            "Mail".to_string()
+        }
+
+        fn types(&self) -> Types {
+            Types::from([
+                (
+                    "Person".to_string(),
+                    vec![field("name", "string"), field("wallet", "address")],
+                ),
+                (
+                    "Mail".to_string(),
+                    vec![
+                        field("from", "Person"),
+                        field("to", "Person"),
+                        field("contents", "string"),
+                    ],
+                ),
+            ])
+        }
+    }
+
+    /// A message with arrays of both struct and elementary types.
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Group {
+        members: Vec<Person>,
+        member_ids: Vec<U256>,
+    }
+
+    impl Eip712 for Group {
+        type Error = std::convert::Infallible;
+
+        fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+            Ok(mail_domain())
+        }
+
+        fn type_hash() -> Result<[u8; 32], Self::Error> {
+            Ok(keccak256(
+                "Group(Person[] members,uint256[] memberIds)Person(string name,address wallet)",
+            ))
+        }
+
+        fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+            let members: Vec<u8> = self.members.iter().flat_map(Person::struct_hash).collect();
+            let member_ids = encode(
+                &self
+                    .member_ids
+                    .iter()
+                    .map(|id| Token::Uint(*id))
+                    .collect::<Vec<_>>(),
+            );
+
+            Ok(keccak256(encode(&[
+                Token::FixedBytes(Self::type_hash()?.to_vec()),
+                Token::FixedBytes(keccak256(members).to_vec()),
+                Token::FixedBytes(keccak256(member_ids).to_vec()),
+            ])))
+        }
+    }
+
+    impl Eip712Types for Group {
+        fn primary_type(&self) -> String {
+            "Group".to_string()
+        }
+
+        fn types(&self) -> Types {
+            Types::from([
+                (
+                    "Person".to_string(),
+                    vec![field("name", "string"), field("wallet", "address")],
+                ),
+                (
+                    "Group".to_string(),
+                    vec![
+                        field("members", "Person[]"),
+                        field("memberIds", "uint256[]"),
+                    ],
+                ),
+            ])
+        }
+    }
+
+    fn person(name: &str, wallet: &str) -> Person {
+        Person {
+            name: name.to_string(),
+            wallet: wallet.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_to_typed_data_nested_struct() {
+        let mail = Mail {
+            from: person("Cow", "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+            to: person("Bob", "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+            contents: "Hello, Bob!".to_string(),
+        };
+
+        let typed_data = mail.to_typed_data().unwrap();
+
+        assert_eq!(
+            hex::encode(typed_data.encode_eip712().unwrap()),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+        assert_eq!(typed_data.types["EIP712Domain"].len(), 4);
+        assert_eq!(typed_data.message["from"]["name"], "Cow");
+    }
+
+    #[test]
+    fn test_to_typed_data_arrays() {
+        let group = Group {
+            members: vec![
+                person("Cow", "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+                person("Bob", "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+            ],
+            member_ids: vec![U256::from(1), U256::from(2)],
+        };
+
+        let typed_data = group.to_typed_data().unwrap();
+
+        assert_eq!(typed_data.primary_type, "Group");
+        assert_eq!(typed_data.message["members"][1]["name"], "Bob");
+        assert_eq!(typed_data.message["memberIds"][0], "0x1");
+    }
+
+    #[test]
+    fn test_typed_data_hashes() {
+        let mail = Mail {
+            from: person("Cow", "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+            to: person("Bob", "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+            contents: "Hello, Bob!".to_string(),
+        };
+        let typed_data = mail.to_typed_data().unwrap();
+
+        assert_eq!(
+            hex::encode(domain_hash(&typed_data).unwrap()),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            hex::encode(struct_hash(&typed_data).unwrap()),
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        assert_eq!(
+            typed_data_hash(&typed_data).unwrap(),
+            typed_data_hash(&mail).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_typed_data_adds_domain_type() {
+        let json = serde_json::json!({
+            "types": {
+                "Person": [{ "name": "name", "type": "string" }, { "name": "wallet", "type": "address" }]
+            },
+            "primaryType": "Person",
+            "domain": { "name": "Ether Mail", "version": "1", "chainId": 1 },
+            "message": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" }
+        });
+        let typed_data: TypedData = serde_json::from_value(json).unwrap();
+
+        let converted = typed_data.to_typed_data().unwrap();
+
+        assert_eq!(
+            converted.types["EIP712Domain"],
+            vec![
+                field("name", "string"),
+                field("version", "string"),
+                field("chainId", "uint256")
+            ]
+        );
+        assert_eq!(
+            converted.encode_eip712().unwrap(),
+            typed_data.encode_eip712().unwrap()
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    client::FrameClient,
+    error::{bail, FrameError, Result},
+};
+use ethers::{
+    middleware::Middleware,
+    providers::{JsonRpcClient, ProviderError},
+    types::Address,
+};
+
+/// The chains with the ENS registry deployed: Ethereum mainnet, Goerli, Holesky and Sepolia.
+pub const ENS_CHAIN_IDS: [u64; 4] = [1, 5, 17000, 11155111];
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Resolves an ENS name, such as `vitalik.eth`, to an address.
+    ///
+    /// The name is resolved on the chain Frame is connected to, which must be one of
+    /// [`ENS_CHAIN_IDS`].
+    ///
+    /// # Parameters
+    /// - `name`: The ENS name to resolve.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `Address` the name resolves to.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///
+    ///     let to = client.resolve_ens("vitalik.eth").await?;
+    ///     client.send_gas_token(from, to, U256::exp10(15)).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `FrameError::EnsNotFound` if the name has no resolver or no address set, and
+    /// another error if the connected chain has no ENS deployment or if the lookup fails.
+    pub async fn resolve_ens(&self, name: &str) -> Result<Address> {
+        self.ensure_ens_chain().await?;
+        let address = self
+            .provider
+            .resolve_name(name)
+            .await
+            .map_err(|err| ens_error(err, name))?;
+        if address.is_zero() {
+            return Err(FrameError::EnsNotFound(name.to_string()));
+        }
+        Ok(address)
+    }
+
+    /// Looks up the primary ENS name of an address (reverse resolution).
+    ///
+    /// The name is only returned if it also resolves back to `address`, so it can be displayed
+    /// safely. Like [`FrameClient::resolve_ens`], the connected chain must have ENS deployed.
+    ///
+    /// # Parameters
+    /// - `address`: The `Address` to look up.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the ENS name of `address`.
+    ///
+    /// # Errors
+    /// Returns `FrameError::EnsNotFound` if the address has no primary name, and another error
+    /// if the connected chain has no ENS deployment, if the name does not resolve back to the
+    /// address, or if the lookup fails.
+    pub async fn lookup_ens(&self, address: Address) -> Result<String> {
+        self.ensure_ens_chain().await?;
+        self.provider
+            .lookup_address(address)
+            .await
+            .map_err(|err| ens_error(err, &format!("{:?}", address)))
+    }
+
+    /// Fails with a descriptive error if the connected chain has no ENS registry.
+    async fn ensure_ens_chain(&self) -> Result<()> {
+        let chain_id = self.get_chain_id().await?;
+        if !ENS_CHAIN_IDS.iter().any(|id| chain_id == (*id).into()) {
+            bail!(
+                "ENS is not available on chain {}; switch Frame to Ethereum mainnet or a testnet",
+                chain_id
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Describes the ENS errors of the provider, which only carry the name of the ENS node.
+fn ens_error(err: ProviderError, name: &str) -> FrameError {
+    match err {
+        ProviderError::EnsError(_) => FrameError::EnsNotFound(name.to_string()),
+        ProviderError::EnsNotOwned(_) => FrameError::Other(anyhow::anyhow!(
+            "The ENS name of {} does not resolve back to it",
+            name
+        )),
+        err => err.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{Bytes, U256};
+
+    #[tokio::test]
+    async fn test_resolve_ens_requires_ens_chain() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push(U256::from(42161)).unwrap();
+
+        let err = client.resolve_ens("vitalik.eth").await.unwrap_err();
+        assert!(err.to_string().contains("chain 42161"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_ens_without_resolver() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push::<Bytes, _>(Bytes::from(vec![0u8; 32])).unwrap();
+        mock.push(U256::from(1)).unwrap();
+
+        let err = client.resolve_ens("unknown.eth").await.unwrap_err();
+        assert!(matches!(err, FrameError::EnsNotFound(name) if name == "unknown.eth"));
+    }
+}
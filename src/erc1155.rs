@@ -0,0 +1,306 @@
+use crate::{
+    abi::function,
+    client::FrameClient,
+    error::{bail, ensure, Result},
+    transport::FrameTransport,
+};
+use ethers::{
+    abi::Token,
+    middleware::Middleware,
+    providers::JsonRpcClient,
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, TransactionRequest, H256, U256,
+    },
+};
+
+const BALANCE_OF: &str = "function balanceOf(address account, uint256 id) view returns (uint256)";
+const BALANCE_OF_BATCH: &str = "function balanceOfBatch(address[] accounts, uint256[] ids) \
+                                view returns (uint256[])";
+const URI: &str = "function uri(uint256 id) view returns (string)";
+const IS_APPROVED_FOR_ALL: &str =
+    "function isApprovedForAll(address account, address operator) view returns (bool)";
+const SAFE_TRANSFER_FROM: &str = "function safeTransferFrom(address from, address to, \
+                                  uint256 id, uint256 amount, bytes data)";
+const SAFE_BATCH_TRANSFER_FROM: &str = "function safeBatchTransferFrom(address from, address to, \
+                                        uint256[] ids, uint256[] amounts, bytes data)";
+const SET_APPROVAL_FOR_ALL: &str = "function setApprovalForAll(address operator, bool approved)";
+
+/// A client for a single ERC-1155 multi-token contract, sending transactions through Frame.
+///
+/// Like [`Erc721Client`](crate::erc721::Erc721Client), the calldata is encoded with
+/// `ethers::abi`. Getters are read-only `eth_call`s; the other methods are signed by `caller`
+/// and wait for the receipt.
+///
+/// # Examples
+/// ```no_run
+/// use ethers::types::{Address, Bytes, U256};
+/// use frame_rs::{client::FrameClient, erc1155::Erc1155Client};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let client = FrameClient::new(U256::from(1), None, None).await?;
+///     let contract: Address = "0x...".parse()?;
+///     let owner = client.get_accounts().await?[0];
+///     let to: Address = "0x...".parse()?;
+///
+///     let items = Erc1155Client::new(client, contract);
+///     let ids = [U256::from(1), U256::from(2)];
+///     let balances = items.balance_of_batch(&[owner, owner], &ids).await?;
+///     items
+///         .safe_batch_transfer_from(owner, owner, to, &ids, &balances, Bytes::new())
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Erc1155Client<P = FrameTransport> {
+    client: FrameClient<P>,
+    contract: Address,
+}
+
+impl<P: JsonRpcClient> Erc1155Client<P> {
+    /// Creates a client for the multi-token contract at `contract`.
+    pub fn new(client: FrameClient<P>, contract: Address) -> Self {
+        Self { client, contract }
+    }
+
+    /// Returns the address of the multi-token contract.
+    pub fn contract(&self) -> Address {
+        self.contract
+    }
+
+    /// Returns the wrapped client.
+    pub fn client(&self) -> &FrameClient<P> {
+        &self.client
+    }
+
+    /// Returns the balance of token `id` held by `account`.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or if the contract does not return a `uint256`.
+    pub async fn balance_of(&self, account: Address, id: U256) -> Result<U256> {
+        match self
+            .call(BALANCE_OF, &[Token::Address(account), Token::Uint(id)])
+            .await?
+        {
+            Token::Uint(balance) => Ok(balance),
+            token => bail!("Unexpected balanceOf output: {:?}", token),
+        }
+    }
+
+    /// Returns the balances of several `(account, id)` pairs in one call. `accounts[i]` is
+    /// paired with `ids[i]`.
+    ///
+    /// # Errors
+    /// Returns an error if `accounts` and `ids` have different lengths, if the call fails or if
+    /// the contract does not return one `uint256` per pair.
+    pub async fn balance_of_batch(&self, accounts: &[Address], ids: &[U256]) -> Result<Vec<U256>> {
+        ensure!(
+            accounts.len() == ids.len(),
+            "Got {} accounts but {} token IDs",
+            accounts.len(),
+            ids.len()
+        );
+        let args = [
+            Token::Array(accounts.iter().copied().map(Token::Address).collect()),
+            uint_array(ids),
+        ];
+        let Token::Array(balances) = self.call(BALANCE_OF_BATCH, &args).await? else {
+            bail!("Unexpected balanceOfBatch output");
+        };
+        ensure!(
+            balances.len() == ids.len(),
+            "balanceOfBatch returned {} balances for {} token IDs",
+            balances.len(),
+            ids.len()
+        );
+        balances
+            .into_iter()
+            .map(|balance| match balance {
+                Token::Uint(balance) => Ok(balance),
+                token => bail!("Unexpected balanceOfBatch output: {:?}", token),
+            })
+            .collect()
+    }
+
+    /// Returns the metadata URI of token `id`. Per the standard, the URI may contain an `{id}`
+    /// placeholder that clients replace with the hex token ID.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or if the contract does not return a `string`.
+    pub async fn uri(&self, id: U256) -> Result<String> {
+        match self.call(URI, &[Token::Uint(id)]).await? {
+            Token::String(uri) => Ok(uri),
+            token => bail!("Unexpected uri output: {:?}", token),
+        }
+    }
+
+    /// Returns `true` if `operator` may transfer all tokens of `owner`.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or if the contract does not return a `bool`.
+    pub async fn is_approved_for_all(&self, owner: Address, operator: Address) -> Result<bool> {
+        let args = [Token::Address(owner), Token::Address(operator)];
+        match self.call(IS_APPROVED_FOR_ALL, &args).await? {
+            Token::Bool(approved) => Ok(approved),
+            token => bail!("Unexpected isApprovedForAll output: {:?}", token),
+        }
+    }
+
+    /// Transfers `amount` of token `id` from `from` to `to`. `caller`, which signs the
+    /// transaction, must be `from` or an approved operator. `data` is passed to the recipient's
+    /// `onERC1155Received` hook if it is a contract.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    pub async fn safe_transfer_from(
+        &self,
+        caller: Address,
+        from: Address,
+        to: Address,
+        id: U256,
+        amount: U256,
+        data: Bytes,
+    ) -> Result<H256> {
+        let args = [
+            Token::Address(from),
+            Token::Address(to),
+            Token::Uint(id),
+            Token::Uint(amount),
+            Token::Bytes(data.to_vec()),
+        ];
+        self.send(caller, SAFE_TRANSFER_FROM, &args).await
+    }
+
+    /// Transfers `amounts[i]` of token `ids[i]` for every `i` from `from` to `to` in one
+    /// transaction. Otherwise behaves like [`Erc1155Client::safe_transfer_from`].
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Errors
+    /// Returns an error if `ids` and `amounts` have different lengths, if the transaction
+    /// fails to be sent or if no receipt is returned for it.
+    pub async fn safe_batch_transfer_from(
+        &self,
+        caller: Address,
+        from: Address,
+        to: Address,
+        ids: &[U256],
+        amounts: &[U256],
+        data: Bytes,
+    ) -> Result<H256> {
+        ensure!(
+            ids.len() == amounts.len(),
+            "Got {} token IDs but {} amounts",
+            ids.len(),
+            amounts.len()
+        );
+        let args = [
+            Token::Address(from),
+            Token::Address(to),
+            uint_array(ids),
+            uint_array(amounts),
+            Token::Bytes(data.to_vec()),
+        ];
+        self.send(caller, SAFE_BATCH_TRANSFER_FROM, &args).await
+    }
+
+    /// Allows or revokes `operator` to transfer all tokens of `caller`, which signs the
+    /// transaction.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    pub async fn set_approval_for_all(
+        &self,
+        caller: Address,
+        operator: Address,
+        approved: bool,
+    ) -> Result<H256> {
+        let args = [Token::Address(operator), Token::Bool(approved)];
+        self.send(caller, SET_APPROVAL_FOR_ALL, &args).await
+    }
+
+    /// Calls a getter with a single output and returns it.
+    async fn call(&self, signature: &str, args: &[Token]) -> Result<Token> {
+        let function = function(signature);
+        let data = function.encode_input(args)?;
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(self.contract)
+            .data(data)
+            .into();
+
+        let output = self.client.provider.call(&tx, None).await?;
+        match function.decode_output(&output)?.as_slice() {
+            [token] => Ok(token.clone()),
+            tokens => bail!("Unexpected {} output: {:?}", function.name, tokens),
+        }
+    }
+
+    async fn send(&self, caller: Address, signature: &str, args: &[Token]) -> Result<H256> {
+        let data = function(signature).encode_input(args)?;
+        let tx = TransactionRequest::new()
+            .from(caller)
+            .to(self.contract)
+            .data(data);
+        self.client.send_and_wait(tx).await
+    }
+}
+
+fn uint_array(values: &[U256]) -> Token {
+    Token::Array(values.iter().copied().map(Token::Uint).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_balance_of_batch() {
+        let (client, mock) = FrameClient::mocked();
+        let balances = vec![U256::from(3), U256::zero()];
+        let output = Bytes::from(ethers::abi::encode(&[uint_array(&balances)]));
+        mock.push::<Bytes, _>(output).unwrap();
+        let contract = Address::random();
+        let accounts = [Address::random(), Address::random()];
+        let ids = [U256::from(1), U256::from(2)];
+
+        let items = Erc1155Client::new(client, contract);
+        assert_eq!(
+            items.balance_of_batch(&accounts, &ids).await.unwrap(),
+            balances
+        );
+        assert!(items.balance_of_batch(&accounts, &ids[..1]).await.is_err());
+
+        let data = function(BALANCE_OF_BATCH)
+            .encode_input(&[
+                Token::Array(accounts.iter().copied().map(Token::Address).collect()),
+                uint_array(&ids),
+            ])
+            .unwrap();
+        let tx: TypedTransaction = TransactionRequest::new().to(contract).data(data).into();
+        mock.assert_request("eth_call", (tx, "latest")).unwrap();
+    }
+
+    #[test]
+    fn test_safe_batch_transfer_from_encoding() {
+        let data = function(SAFE_BATCH_TRANSFER_FROM)
+            .encode_input(&[
+                Token::Address(Address::zero()),
+                Token::Address(Address::zero()),
+                uint_array(&[U256::from(1), U256::from(2)]),
+                uint_array(&[U256::from(10), U256::from(20)]),
+                Token::Bytes(vec![]),
+            ])
+            .unwrap();
+        assert_eq!(ethers::utils::hex::encode(&data[..4]), "2eb2c2d6");
+        // Selector, two addresses, three offsets, two arrays of two and an empty `bytes`.
+        assert_eq!(data.len(), 4 + 32 * (2 + 3 + 3 + 3 + 1));
+    }
+}
@@ -0,0 +1,465 @@
+use crate::{
+    abi::function,
+    client::FrameClient,
+    error::{bail, Result},
+    transport::FrameTransport,
+};
+use ethers::{
+    abi::Token,
+    middleware::Middleware,
+    providers::JsonRpcClient,
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, TransactionRequest, H256, U256,
+    },
+};
+
+const TRANSFER: &str = "function transfer(address to, uint256 amount) returns (bool)";
+const APPROVE: &str = "function approve(address spender, uint256 amount) returns (bool)";
+const TRANSFER_FROM: &str =
+    "function transferFrom(address from, address to, uint256 amount) returns (bool)";
+const BALANCE_OF: &str = "function balanceOf(address owner) view returns (uint256)";
+const ALLOWANCE: &str = "function allowance(address owner, address spender) view returns (uint256)";
+const TOTAL_SUPPLY: &str = "function totalSupply() view returns (uint256)";
+pub(crate) const NAME: &str = "function name() view returns (string)";
+const SYMBOL: &str = "function symbol() view returns (string)";
+const DECIMALS: &str = "function decimals() view returns (uint8)";
+
+/// The optional metadata getters of an ERC-20 token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Erc20Metadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// Decodes the output of a `name()` or `symbol()` getter.
+///
+/// Falls back to a NUL-padded `bytes32` for old tokens such as MKR that predate the `string`
+/// return type.
+pub(crate) fn decode_string_output(output: &[u8]) -> Result<String> {
+    if let Ok(tokens) = function(NAME).decode_output(output) {
+        if let [Token::String(value)] = tokens.as_slice() {
+            return Ok(value.clone());
+        }
+    }
+    if output.len() != 32 {
+        bail!(
+            "Unexpected string output: 0x{}",
+            ethers::utils::hex::encode(output)
+        );
+    }
+    let end = output.iter().position(|&byte| byte == 0).unwrap_or(32);
+    Ok(String::from_utf8(output[..end].to_vec())?)
+}
+
+/// Encodes the calldata of an ERC-20 `transfer(address,uint256)` call.
+pub(crate) fn transfer_calldata(to: Address, amount: U256) -> Result<Bytes> {
+    let data = function(TRANSFER).encode_input(&[Token::Address(to), Token::Uint(amount)])?;
+    Ok(data.into())
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Transfers an amount of an ERC-20 token from one address to another.
+    ///
+    /// This builds the `transfer(address,uint256)` calldata, sends it to the `token` contract
+    /// through Frame and waits for the transaction receipt, like
+    /// [`FrameClient::send_gas_token`].
+    ///
+    /// # Parameters
+    /// - `token`: The `Address` of the ERC-20 token contract.
+    /// - `from`: The `Address` the tokens are transferred from, which signs the transaction.
+    /// - `to`: The `Address` the tokens are transferred to.
+    /// - `amount`: The amount to transfer, in the token's base units.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash of the transfer.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse()?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let tx_hash = client.send_erc20(usdc, from, to, U256::from(1_000_000)).await?; // 1 USDC
+    ///     println!("Transaction hash: {:?}", tx_hash);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    pub async fn send_erc20(
+        &self,
+        token: Address,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<H256> {
+        let tx = TransactionRequest::new()
+            .from(from)
+            .to(token)
+            .data(transfer_calldata(to, amount)?);
+        self.send_and_wait(tx).await
+    }
+
+    /// Retrieves the ERC-20 token balance of an address.
+    ///
+    /// This performs a read-only `eth_call` of `balanceOf(address)` against the token contract,
+    /// so no signer is required.
+    ///
+    /// # Parameters
+    /// - `token`: The `Address` of the ERC-20 token contract.
+    /// - `owner`: The `Address` whose balance is queried.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the raw balance in the token's base units.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse()?;
+    ///     let owner = client.get_accounts().await?[0];
+    ///
+    ///     let balance = client.erc20_balance(usdc, owner).await?;
+    ///     println!("USDC balance: {}", balance);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or if the contract does not return a `uint256`.
+    pub async fn erc20_balance(&self, token: Address, owner: Address) -> Result<U256> {
+        let balance_of = function(BALANCE_OF);
+        let data = balance_of.encode_input(&[Token::Address(owner)])?;
+        let tx: TypedTransaction = TransactionRequest::new().to(token).data(data).into();
+
+        let output = self.provider.call(&tx, None).await?;
+        match balance_of.decode_output(&output)?.as_slice() {
+            [Token::Uint(balance)] => Ok(*balance),
+            tokens => bail!("Unexpected balanceOf output: {:?}", tokens),
+        }
+    }
+
+    /// Retrieves the name, symbol and decimals of an ERC-20 token.
+    ///
+    /// This performs read-only `eth_call`s of the `name()`, `symbol()` and `decimals()` getters.
+    /// Tokens that return `bytes32` instead of `string` for their name and symbol are supported.
+    ///
+    /// # Parameters
+    /// - `token`: The `Address` of the ERC-20 token contract.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the token's `Erc20Metadata`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::{types::{Address, U256}, utils::format_units};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse()?;
+    ///     let owner = client.get_accounts().await?[0];
+    ///
+    ///     let metadata = client.erc20_metadata(usdc).await?;
+    ///     let balance = client.erc20_balance(usdc, owner).await?;
+    ///     println!(
+    ///         "{} {}",
+    ///         format_units(balance, u32::from(metadata.decimals))?,
+    ///         metadata.symbol
+    ///     );
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if any of the calls fail or if a getter returns an unexpected value.
+    pub async fn erc20_metadata(&self, token: Address) -> Result<Erc20Metadata> {
+        let name = decode_string_output(&self.erc20_getter(token, NAME).await?)?;
+        let symbol = decode_string_output(&self.erc20_getter(token, SYMBOL).await?)?;
+
+        let decimals = function(DECIMALS);
+        let output = self.erc20_getter(token, DECIMALS).await?;
+        let decimals = match decimals.decode_output(&output)?.as_slice() {
+            [Token::Uint(decimals)] if *decimals <= U256::from(u8::MAX) => decimals.as_u32() as u8,
+            tokens => bail!("Unexpected decimals output: {:?}", tokens),
+        };
+
+        Ok(Erc20Metadata {
+            name,
+            symbol,
+            decimals,
+        })
+    }
+
+    /// Calls a parameterless getter on `token` and returns the raw output.
+    pub(crate) async fn erc20_getter(&self, token: Address, signature: &str) -> Result<Bytes> {
+        let data = function(signature).encode_input(&[])?;
+        let tx: TypedTransaction = TransactionRequest::new().to(token).data(data).into();
+        Ok(self.provider.call(&tx, None).await?)
+    }
+}
+
+/// A client for a single ERC-20 token contract, sending transactions through Frame.
+///
+/// The calldata of each method is encoded with `ethers::abi`, so no bindings need to be
+/// generated. Getters are read-only `eth_call`s; the other methods send a transaction and wait
+/// for its receipt, like [`FrameClient::send_erc20`].
+///
+/// # Examples
+/// ```no_run
+/// use ethers::types::{Address, U256};
+/// use frame_rs::{client::FrameClient, erc20::Erc20Client};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let client = FrameClient::new(U256::from(1), None, None).await?;
+///     let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse()?;
+///     let owner = client.get_accounts().await?[0];
+///     let router: Address = "0x...".parse()?;
+///
+///     let usdc = Erc20Client::new(client, usdc);
+///     if usdc.allowance(owner, router).await? < U256::from(1_000_000) {
+///         usdc.approve(owner, router, U256::from(1_000_000)).await?;
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Erc20Client<P = FrameTransport> {
+    client: FrameClient<P>,
+    token: Address,
+}
+
+impl<P: JsonRpcClient> Erc20Client<P> {
+    /// Creates a client for the token contract at `token`.
+    pub fn new(client: FrameClient<P>, token: Address) -> Self {
+        Self { client, token }
+    }
+
+    /// Returns the address of the token contract.
+    pub fn token(&self) -> Address {
+        self.token
+    }
+
+    /// Returns the wrapped client.
+    pub fn client(&self) -> &FrameClient<P> {
+        &self.client
+    }
+
+    /// Returns the token balance of `owner`, in base units.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or if the contract does not return a `uint256`.
+    pub async fn balance_of(&self, owner: Address) -> Result<U256> {
+        self.client.erc20_balance(self.token, owner).await
+    }
+
+    /// Returns how many tokens `spender` may still transfer on behalf of `owner`.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or if the contract does not return a `uint256`.
+    pub async fn allowance(&self, owner: Address, spender: Address) -> Result<U256> {
+        self.call_uint(ALLOWANCE, &[Token::Address(owner), Token::Address(spender)])
+            .await
+    }
+
+    /// Returns the total supply of the token, in base units.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or if the contract does not return a `uint256`.
+    pub async fn total_supply(&self) -> Result<U256> {
+        self.call_uint(TOTAL_SUPPLY, &[]).await
+    }
+
+    /// Returns the name of the token.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or if the contract returns neither a `string` nor a
+    /// `bytes32`.
+    pub async fn name(&self) -> Result<String> {
+        decode_string_output(&self.client.erc20_getter(self.token, NAME).await?)
+    }
+
+    /// Returns the symbol of the token.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or if the contract returns neither a `string` nor a
+    /// `bytes32`.
+    pub async fn symbol(&self) -> Result<String> {
+        decode_string_output(&self.client.erc20_getter(self.token, SYMBOL).await?)
+    }
+
+    /// Returns the number of decimals of the token's display unit.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or if the contract does not return a `uint8`.
+    pub async fn decimals(&self) -> Result<u8> {
+        match self.call_uint(DECIMALS, &[]).await? {
+            decimals if decimals <= U256::from(u8::MAX) => Ok(decimals.as_u32() as u8),
+            decimals => bail!("Unexpected decimals output: {}", decimals),
+        }
+    }
+
+    /// Transfers `amount` tokens from `from`, which signs the transaction, to `to`.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    pub async fn transfer(&self, from: Address, to: Address, amount: U256) -> Result<H256> {
+        self.client.send_erc20(self.token, from, to, amount).await
+    }
+
+    /// Allows `spender` to transfer up to `amount` tokens on behalf of `owner`, which signs the
+    /// transaction. The allowance replaces any previous one.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    pub async fn approve(&self, owner: Address, spender: Address, amount: U256) -> Result<H256> {
+        self.send(
+            owner,
+            APPROVE,
+            &[Token::Address(spender), Token::Uint(amount)],
+        )
+        .await
+    }
+
+    /// Transfers `amount` tokens from `from` to `to` using the allowance of `spender`, which
+    /// signs the transaction.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    pub async fn transfer_from(
+        &self,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<H256> {
+        let args = [
+            Token::Address(from),
+            Token::Address(to),
+            Token::Uint(amount),
+        ];
+        self.send(spender, TRANSFER_FROM, &args).await
+    }
+
+    async fn call_uint(&self, signature: &str, args: &[Token]) -> Result<U256> {
+        let function = function(signature);
+        let data = function.encode_input(args)?;
+        let tx: TypedTransaction = TransactionRequest::new().to(self.token).data(data).into();
+
+        let output = self.client.provider.call(&tx, None).await?;
+        match function.decode_output(&output)?.as_slice() {
+            [Token::Uint(value)] => Ok(*value),
+            tokens => bail!("Unexpected {} output: {:?}", function.name, tokens),
+        }
+    }
+
+    async fn send(&self, from: Address, signature: &str, args: &[Token]) -> Result<H256> {
+        let data = function(signature).encode_input(args)?;
+        let tx = TransactionRequest::new()
+            .from(from)
+            .to(self.token)
+            .data(data);
+        self.client.send_and_wait(tx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::utils::hex;
+
+    #[test]
+    fn test_transfer_calldata() {
+        let to: Address = "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+            .parse()
+            .unwrap();
+        let data = transfer_calldata(to, U256::from(1_000_000)).unwrap();
+
+        assert_eq!(
+            hex::encode(data),
+            "a9059cbb\
+             000000000000000000000000bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\
+             00000000000000000000000000000000000000000000000000000000000f4240"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_erc20_balance() {
+        let (client, mock) = FrameClient::mocked();
+        let output = Bytes::from(ethers::abi::encode(&[Token::Uint(U256::from(42))]));
+        mock.push::<Bytes, _>(output).unwrap();
+
+        let balance = client
+            .erc20_balance(Address::random(), Address::random())
+            .await
+            .unwrap();
+        assert_eq!(balance, U256::from(42));
+    }
+
+    #[tokio::test]
+    async fn test_erc20_client_allowance() {
+        let (client, mock) = FrameClient::mocked();
+        let output = Bytes::from(ethers::abi::encode(&[Token::Uint(U256::from(500))]));
+        mock.push::<Bytes, _>(output).unwrap();
+        let token = Address::random();
+        let (owner, spender) = (Address::random(), Address::random());
+
+        let allowance = Erc20Client::new(client, token)
+            .allowance(owner, spender)
+            .await
+            .unwrap();
+        assert_eq!(allowance, U256::from(500));
+
+        let data = function(ALLOWANCE)
+            .encode_input(&[Token::Address(owner), Token::Address(spender)])
+            .unwrap();
+        let tx: TypedTransaction = TransactionRequest::new().to(token).data(data).into();
+        mock.assert_request("eth_call", (tx, "latest")).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_erc20_metadata_with_bytes32_symbol() {
+        let (client, mock) = FrameClient::mocked();
+        let decimals = Bytes::from(ethers::abi::encode(&[Token::Uint(U256::from(18))]));
+        let mut symbol = [0u8; 32];
+        symbol[..3].copy_from_slice(b"MKR");
+        let symbol = Bytes::from(symbol.to_vec());
+        let name = Bytes::from(ethers::abi::encode(&[Token::String("Maker".to_string())]));
+        mock.push::<Bytes, _>(decimals).unwrap();
+        mock.push::<Bytes, _>(symbol).unwrap();
+        mock.push::<Bytes, _>(name).unwrap();
+
+        let metadata = client.erc20_metadata(Address::random()).await.unwrap();
+        assert_eq!(
+            metadata,
+            Erc20Metadata {
+                name: "Maker".to_string(),
+                symbol: "MKR".to_string(),
+                decimals: 18,
+            }
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use crate::{
+    abi::function,
+    client::FrameClient,
+    error::{bail, Result},
+    transport::FrameTransport,
+};
+use ethers::{
+    abi::Token,
+    middleware::Middleware,
+    providers::JsonRpcClient,
+    types::{transaction::eip2718::TypedTransaction, Address, TransactionRequest, H256, U256},
+};
+
+const BALANCE_OF: &str = "function balanceOf(address owner) view returns (uint256)";
+const OWNER_OF: &str = "function ownerOf(uint256 tokenId) view returns (address)";
+const TOKEN_URI: &str = "function tokenURI(uint256 tokenId) view returns (string)";
+const GET_APPROVED: &str = "function getApproved(uint256 tokenId) view returns (address)";
+const IS_APPROVED_FOR_ALL: &str =
+    "function isApprovedForAll(address owner, address operator) view returns (bool)";
+const TRANSFER_FROM: &str = "function transferFrom(address from, address to, uint256 tokenId)";
+const SAFE_TRANSFER_FROM: &str =
+    "function safeTransferFrom(address from, address to, uint256 tokenId)";
+const APPROVE: &str = "function approve(address to, uint256 tokenId)";
+const SET_APPROVAL_FOR_ALL: &str = "function setApprovalForAll(address operator, bool approved)";
+
+/// A client for a single ERC-721 (NFT) contract, sending transactions through Frame.
+///
+/// Like [`Erc20Client`](crate::erc20::Erc20Client), the calldata is encoded with
+/// `ethers::abi`. Getters are read-only `eth_call`s; the other methods are signed by `caller`
+/// and wait for the receipt.
+///
+/// # Examples
+/// ```no_run
+/// use ethers::types::{Address, U256};
+/// use frame_rs::{client::FrameClient, erc721::Erc721Client};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let client = FrameClient::new(U256::from(1), None, None).await?;
+///     let contract: Address = "0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D".parse()?;
+///     let owner = client.get_accounts().await?[0];
+///     let to: Address = "0x...".parse()?;
+///
+///     let nft = Erc721Client::new(client, contract);
+///     let token_id = U256::from(1234);
+///     if nft.owner_of(token_id).await? == owner {
+///         nft.safe_transfer_from(owner, owner, to, token_id).await?;
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Erc721Client<P = FrameTransport> {
+    client: FrameClient<P>,
+    contract: Address,
+}
+
+impl<P: JsonRpcClient> Erc721Client<P> {
+    /// Creates a client for the NFT contract at `contract`.
+    pub fn new(client: FrameClient<P>, contract: Address) -> Self {
+        Self { client, contract }
+    }
+
+    /// Returns the address of the NFT contract.
+    pub fn contract(&self) -> Address {
+        self.contract
+    }
+
+    /// Returns the wrapped client.
+    pub fn client(&self) -> &FrameClient<P> {
+        &self.client
+    }
+
+    /// Returns the number of tokens owned by `owner`.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or if the contract does not return a `uint256`.
+    pub async fn balance_of(&self, owner: Address) -> Result<U256> {
+        match self.call(BALANCE_OF, &[Token::Address(owner)]).await? {
+            Token::Uint(balance) => Ok(balance),
+            token => bail!("Unexpected balanceOf output: {:?}", token),
+        }
+    }
+
+    /// Returns the owner of `token_id`.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails, e.g. because the token does not exist, or if the
+    /// contract does not return an `address`.
+    pub async fn owner_of(&self, token_id: U256) -> Result<Address> {
+        match self.call(OWNER_OF, &[Token::Uint(token_id)]).await? {
+            Token::Address(owner) => Ok(owner),
+            token => bail!("Unexpected ownerOf output: {:?}", token),
+        }
+    }
+
+    /// Returns the metadata URI of `token_id`.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails, e.g. because the contract doesn't implement the
+    /// metadata extension, or if the contract does not return a `string`.
+    pub async fn token_uri(&self, token_id: U256) -> Result<String> {
+        match self.call(TOKEN_URI, &[Token::Uint(token_id)]).await? {
+            Token::String(uri) => Ok(uri),
+            token => bail!("Unexpected tokenURI output: {:?}", token),
+        }
+    }
+
+    /// Returns the address approved to transfer `token_id`, or the zero address if none is.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or if the contract does not return an `address`.
+    pub async fn get_approved(&self, token_id: U256) -> Result<Address> {
+        match self.call(GET_APPROVED, &[Token::Uint(token_id)]).await? {
+            Token::Address(approved) => Ok(approved),
+            token => bail!("Unexpected getApproved output: {:?}", token),
+        }
+    }
+
+    /// Returns `true` if `operator` may transfer all tokens of `owner`.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or if the contract does not return a `bool`.
+    pub async fn is_approved_for_all(&self, owner: Address, operator: Address) -> Result<bool> {
+        let args = [Token::Address(owner), Token::Address(operator)];
+        match self.call(IS_APPROVED_FOR_ALL, &args).await? {
+            Token::Bool(approved) => Ok(approved),
+            token => bail!("Unexpected isApprovedForAll output: {:?}", token),
+        }
+    }
+
+    /// Transfers `token_id` from `from` to `to` with `transferFrom`. `caller`, which signs the
+    /// transaction, must be the owner or approved.
+    ///
+    /// Unlike [`Erc721Client::safe_transfer_from`], this does not check that a contract
+    /// recipient can handle NFTs, so the token may be locked forever.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    pub async fn transfer_from(
+        &self,
+        caller: Address,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<H256> {
+        let args = [
+            Token::Address(from),
+            Token::Address(to),
+            Token::Uint(token_id),
+        ];
+        self.send(caller, TRANSFER_FROM, &args).await
+    }
+
+    /// Transfers `token_id` from `from` to `to` with `safeTransferFrom`, which reverts if `to`
+    /// is a contract that does not accept ERC-721 tokens. `caller`, which signs the
+    /// transaction, must be the owner or approved.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    pub async fn safe_transfer_from(
+        &self,
+        caller: Address,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<H256> {
+        let args = [
+            Token::Address(from),
+            Token::Address(to),
+            Token::Uint(token_id),
+        ];
+        self.send(caller, SAFE_TRANSFER_FROM, &args).await
+    }
+
+    /// Approves `to` to transfer `token_id`, replacing any previous approval. `caller` signs the
+    /// transaction.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    pub async fn approve(&self, caller: Address, to: Address, token_id: U256) -> Result<H256> {
+        let args = [Token::Address(to), Token::Uint(token_id)];
+        self.send(caller, APPROVE, &args).await
+    }
+
+    /// Allows or revokes `operator` to transfer all tokens of `caller`, which signs the
+    /// transaction.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent or if no receipt is returned for it.
+    pub async fn set_approval_for_all(
+        &self,
+        caller: Address,
+        operator: Address,
+        approved: bool,
+    ) -> Result<H256> {
+        let args = [Token::Address(operator), Token::Bool(approved)];
+        self.send(caller, SET_APPROVAL_FOR_ALL, &args).await
+    }
+
+    /// Calls a getter with a single output and returns it.
+    async fn call(&self, signature: &str, args: &[Token]) -> Result<Token> {
+        let function = function(signature);
+        let data = function.encode_input(args)?;
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(self.contract)
+            .data(data)
+            .into();
+
+        let output = self.client.provider.call(&tx, None).await?;
+        match function.decode_output(&output)?.as_slice() {
+            [token] => Ok(token.clone()),
+            tokens => bail!("Unexpected {} output: {:?}", function.name, tokens),
+        }
+    }
+
+    async fn send(&self, caller: Address, signature: &str, args: &[Token]) -> Result<H256> {
+        let data = function(signature).encode_input(args)?;
+        let tx = TransactionRequest::new()
+            .from(caller)
+            .to(self.contract)
+            .data(data);
+        self.client.send_and_wait(tx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{types::Bytes, utils::hex};
+
+    #[tokio::test]
+    async fn test_owner_of() {
+        let (client, mock) = FrameClient::mocked();
+        let owner = Address::random();
+        let output = Bytes::from(ethers::abi::encode(&[Token::Address(owner)]));
+        mock.push::<Bytes, _>(output).unwrap();
+
+        let nft = Erc721Client::new(client, Address::random());
+        assert_eq!(nft.owner_of(U256::from(7)).await.unwrap(), owner);
+    }
+
+    #[test]
+    fn test_safe_transfer_from_selector() {
+        // safeTransferFrom is overloaded; the three-argument variant must be encoded.
+        let data = function(SAFE_TRANSFER_FROM)
+            .encode_input(&[
+                Token::Address(Address::zero()),
+                Token::Address(Address::zero()),
+                Token::Uint(U256::one()),
+            ])
+            .unwrap();
+        assert_eq!(hex::encode(&data[..4]), "42842e0e");
+    }
+}
@@ -0,0 +1,367 @@
+use ethers::{
+    abi::{ParamType, Token},
+    providers::{HttpClientError, ProviderError, RpcError},
+    types::{Bytes, Signature, H256, U256, U64},
+};
+use thiserror::Error;
+
+/// EIP-1193 error code returned when the user rejects a request in Frame.
+pub const USER_REJECTED_CODE: i64 = 4001;
+
+/// EIP-3326 error code returned when the requested chain is not configured in Frame.
+pub const UNRECOGNIZED_CHAIN_CODE: i64 = 4902;
+
+/// JSON-RPC error code returned when the request parameters are malformed.
+pub const INVALID_PARAMS_CODE: i64 = -32602;
+
+/// JSON-RPC error code returned when the node does not implement the requested method.
+pub const METHOD_NOT_FOUND_CODE: i64 = -32601;
+
+/// The selector of Solidity's `Error(string)` revert data.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// The selector of Solidity's `Panic(uint256)` revert data.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// The error type returned by every fallible `frame_rs` operation.
+///
+/// Frame answers most wallet requests with HTTP 200 and reports failures in the `error`
+/// member of the response body. Those errors are surfaced as [`FrameError::UserRejected`] when
+/// the user rejected the request in the Frame UI and as [`FrameError::RpcError`] otherwise, so
+/// callers can tell a deliberate rejection apart from an actual failure.
+#[derive(Debug, Error)]
+pub enum FrameError {
+    /// The user rejected the request in the Frame UI (EIP-1193 code 4001).
+    #[error("the request was rejected in Frame")]
+    UserRejected,
+    /// The requested chain is not configured in Frame (EIP-3326 code 4902).
+    #[error("chain {0} is not configured in Frame")]
+    ChainNotFound(U256),
+    /// Frame could not be reached.
+    #[error("failed to connect to Frame: {0}")]
+    ConnectionFailed(String),
+    /// Frame or the node answered with a JSON-RPC error object.
+    #[error("JSON-RPC error {code}: {message}")]
+    RpcError { code: i64, message: String },
+    /// The user rejected request `index` of a batch in the Frame UI. `signatures` holds the
+    /// signatures of the requests before it, in order.
+    #[error("request {index} of the batch was rejected in Frame")]
+    BatchRejected {
+        index: usize,
+        signatures: Vec<Signature>,
+    },
+    /// Transaction `index` of a batch failed, e.g. because the user rejected it in Frame, and
+    /// the transactions after it were not sent. `submitted` holds the hashes of the
+    /// transactions before it, in order.
+    #[error("transaction {index} of the batch failed: {source}")]
+    BatchFailed {
+        index: usize,
+        submitted: Vec<H256>,
+        source: Box<FrameError>,
+    },
+    /// The transaction or call reverted. `reason` is the decoded revert reason when the node
+    /// returned one, and the node's error message otherwise. `data` is the raw revert data, e.g.
+    /// for decoding custom errors.
+    #[error("execution reverted: {reason}")]
+    ExecutionReverted { reason: String, data: Option<Bytes> },
+    /// A separate broadcast endpoint could not be reached, timed out or sent an invalid response.
+    #[error("failed to broadcast through {url}: {message}")]
+    BroadcastFailed { url: String, message: String },
+    /// A broadcast endpoint reported a different hash than the signed transaction's.
+    #[error("broadcast returned transaction hash {actual:?}, expected {expected:?}")]
+    HashMismatch { expected: H256, actual: H256 },
+    /// A transaction sent through Frame was mined but reverted (receipt status 0). The hash can
+    /// still be looked up on a block explorer.
+    /// `reason` is recovered by replaying the transaction, if that succeeds.
+    #[error(
+        "transaction {hash:?} reverted{}",
+        reason.as_ref().map(|reason| format!(": {}", reason)).unwrap_or_default()
+    )]
+    TransactionReverted {
+        hash: H256,
+        gas_used: Option<U256>,
+        reason: Option<String>,
+    },
+    /// A transaction could not be replaced or cancelled because it was already mined.
+    #[error("transaction {tx_hash:?} was already mined in block {block} and can't be replaced")]
+    AlreadyMined { tx_hash: H256, block: U64 },
+    /// [`FrameClient::sign_hash_unsafe`](crate::client::FrameClient::sign_hash_unsafe) was
+    /// called on a client that was not built with `allow_unsafe_signing(true)`.
+    #[error(
+        "eth_sign is disabled: signing a raw hash is blind signing, and the hash may be a \
+         transaction or permit; enable it with FrameClientBuilder::allow_unsafe_signing"
+    )]
+    UnsafeSigningDisabled,
+    /// Frame refused to blind-sign a raw hash with `eth_sign`, e.g. because the account's
+    /// signer does not allow it.
+    #[error("Frame refused to blind-sign the hash: {0}")]
+    BlindSigningRejected(String),
+    /// An ENS name has no resolver or no address set, or an address has no primary ENS name.
+    #[error("no ENS record found for {0}")]
+    EnsNotFound(String),
+    /// A transaction was rejected by the client's before-send hook, with the hook's reason.
+    #[error("the transaction was rejected before sending: {0}")]
+    SendRejected(String),
+    /// A pending transaction is no longer known to the node, e.g. because it was evicted from
+    /// the mempool, and was not mined.
+    #[error("transaction {hash:?} was dropped before it was mined")]
+    TransactionDropped { hash: H256 },
+    /// A pending transaction left the mempool without being mined because another transaction
+    /// with the same nonce was mined, e.g. a replacement sent with
+    /// [`FrameClient::speed_up`](crate::client::FrameClient::speed_up) or
+    /// [`FrameClient::cancel`](crate::client::FrameClient::cancel).
+    #[error(
+        "transaction {hash:?} was replaced: its nonce {nonce} was used by another transaction"
+    )]
+    TransactionReplaced { hash: H256, nonce: U256 },
+    /// Frame is connected to chain `actual`, but the client expects chain `expected`, e.g.
+    /// because the network was switched in the Frame UI. Nothing was sent.
+    #[error("Frame is connected to chain {actual}, expected chain {expected}")]
+    ChainMismatch { expected: U256, actual: U256 },
+    /// A transaction broke `rule` of the client's [`TxPolicy`](crate::policy::TxPolicy) and
+    /// was not sent.
+    #[error("transaction violates policy rule {rule}: {message}")]
+    PolicyViolation { rule: &'static str, message: String },
+    /// The node doesn't implement a method this operation relies on, e.g. `eth_createAccessList`
+    /// on a chain without EIP-2930 support.
+    #[error("the connected node does not support {0}")]
+    Unsupported(String),
+    /// A request to Frame exceeded the client's request timeout, or waiting for a transaction
+    /// timed out. `tx_hash` is set when the transaction was already submitted, so it can still
+    /// be tracked.
+    #[error(
+        "timed out{}",
+        tx_hash.map(|hash| format!(" waiting for transaction {:?}", hash)).unwrap_or_default()
+    )]
+    Timeout { tx_hash: Option<H256> },
+    /// The provider failed without a JSON-RPC error object, e.g. on a transport failure.
+    #[error(transparent)]
+    ProviderError(ProviderError),
+    /// Any other failure, such as malformed input or an unexpected response.
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+/// A `Result` whose error type defaults to [`FrameError`].
+pub type Result<T, E = FrameError> = std::result::Result<T, E>;
+
+impl FrameError {
+    /// Builds the error for a JSON-RPC error object with the given `code` and `message`.
+    pub(crate) fn from_rpc(code: i64, message: String) -> Self {
+        match code {
+            USER_REJECTED_CODE => FrameError::UserRejected,
+            code => FrameError::RpcError { code, message },
+        }
+    }
+
+    /// Builds the error for a failed call or gas estimation, turning a revert reported by the
+    /// node into [`FrameError::ExecutionReverted`].
+    pub(crate) fn from_revert(err: ProviderError) -> Self {
+        let Some(error) = err.as_error_response() else {
+            return err.into();
+        };
+        if !error.is_revert() {
+            return err.into();
+        }
+
+        let data = error.as_revert_data();
+        let reason = data
+            .as_deref()
+            .and_then(decode_revert_reason)
+            .unwrap_or_else(|| {
+                let message = error.message.as_str();
+                message
+                    .strip_prefix("execution reverted: ")
+                    .unwrap_or(message)
+                    .to_string()
+            });
+        FrameError::ExecutionReverted { reason, data }
+    }
+
+    /// Returns `true` if Frame or the node answered the request with an error, so it was
+    /// refused rather than possibly still being processed, as after a timeout or a transport
+    /// failure.
+    pub(crate) fn is_refusal(&self) -> bool {
+        matches!(
+            self,
+            FrameError::UserRejected
+                | FrameError::ChainNotFound(_)
+                | FrameError::RpcError { .. }
+                | FrameError::ExecutionReverted { .. }
+        )
+    }
+
+    /// Returns `true` if the user rejected the request in the Frame UI.
+    pub fn is_user_rejected(&self) -> bool {
+        matches!(self, FrameError::UserRejected)
+    }
+
+    /// Returns `true` if Frame rejected the request parameters as malformed, for example
+    /// typed data that does not match its declared types.
+    pub fn is_invalid_params(&self) -> bool {
+        matches!(self, FrameError::RpcError { code, .. } if *code == INVALID_PARAMS_CODE)
+    }
+
+    /// Returns `true` if the node does not implement the requested method, for example
+    /// `eth_createAccessList` on a chain without EIP-2930 support. This includes
+    /// [`FrameError::Unsupported`].
+    pub fn is_method_not_found(&self) -> bool {
+        match self {
+            FrameError::RpcError { code, .. } => *code == METHOD_NOT_FOUND_CODE,
+            FrameError::Unsupported(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl From<ProviderError> for FrameError {
+    fn from(err: ProviderError) -> Self {
+        if let Some(err) = reqwest_error(&err) {
+            if is_request_timeout(err) {
+                return FrameError::Timeout { tx_hash: None };
+            }
+        }
+        match err.as_error_response() {
+            Some(error) => FrameError::from_rpc(error.code, error.message.clone()),
+            None => FrameError::ProviderError(err),
+        }
+    }
+}
+
+impl From<reqwest::Error> for FrameError {
+    fn from(err: reqwest::Error) -> Self {
+        if is_request_timeout(&err) {
+            return FrameError::Timeout { tx_hash: None };
+        }
+        FrameError::ConnectionFailed(err.to_string())
+    }
+}
+
+/// Returns the HTTP error behind a provider error, if the provider's transport failed with one.
+fn reqwest_error(err: &ProviderError) -> Option<&reqwest::Error> {
+    match err {
+        ProviderError::HTTPError(err) => Some(err),
+        ProviderError::JsonRpcClientError(err) => {
+            let err: &(dyn std::error::Error + 'static) = err.as_ref();
+            match err.downcast_ref::<HttpClientError>()? {
+                HttpClientError::ReqwestError(err) => Some(err),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns `true` if `err` is the expiry of the request timeout. A connect timeout means Frame
+/// could not be reached and is not included.
+fn is_request_timeout(err: &reqwest::Error) -> bool {
+    err.is_timeout() && !err.is_connect()
+}
+
+impl From<anyhow::Error> for FrameError {
+    fn from(err: anyhow::Error) -> Self {
+        FrameError::Other(err)
+    }
+}
+
+/// Decodes the reason of `Error(string)` and `Panic(uint256)` revert data.
+pub(crate) fn decode_revert_reason(data: &[u8]) -> Option<String> {
+    let (selector, args) = (data.get(..4)?, &data[4..]);
+    if selector == ERROR_SELECTOR {
+        match ethers::abi::decode(&[ParamType::String], args)
+            .ok()?
+            .as_slice()
+        {
+            [Token::String(reason)] => Some(reason.clone()),
+            _ => None,
+        }
+    } else if selector == PANIC_SELECTOR {
+        match ethers::abi::decode(&[ParamType::Uint(256)], args)
+            .ok()?
+            .as_slice()
+        {
+            [Token::Uint(code)] => Some(format!("panic code {:#x}", code)),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+macro_rules! impl_from_other {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl From<$ty> for FrameError {
+                fn from(err: $ty) -> Self {
+                    FrameError::Other(err.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_other!(
+    serde_json::Error,
+    ethers::abi::Error,
+    ethers::types::transaction::eip712::Eip712Error,
+    ethers::types::SignatureError,
+    std::string::FromUtf8Error,
+);
+
+/// Returns early with a [`FrameError::Other`] built from a format string, like `anyhow::bail!`.
+macro_rules! bail {
+    ($($arg:tt)*) => {
+        return Err($crate::error::FrameError::Other(anyhow::anyhow!($($arg)*)))
+    };
+}
+
+/// Returns early with a [`FrameError::Other`] unless the condition holds, like
+/// `anyhow::ensure!`.
+macro_rules! ensure {
+    ($cond:expr, $($arg:tt)*) => {
+        if !$cond {
+            $crate::error::bail!($($arg)*);
+        }
+    };
+}
+
+pub(crate) use {bail, ensure};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::providers::{JsonRpcError, MockResponse};
+
+    #[test]
+    fn test_from_rpc_codes() {
+        assert!(
+            FrameError::from_rpc(USER_REJECTED_CODE, "User rejected".to_string())
+                .is_user_rejected()
+        );
+        assert!(
+            FrameError::from_rpc(INVALID_PARAMS_CODE, "Invalid params".to_string())
+                .is_invalid_params()
+        );
+        assert!(matches!(
+            FrameError::from_rpc(-32000, "execution reverted".to_string()),
+            FrameError::RpcError { code: -32000, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_provider_error_response_is_mapped() {
+        use ethers::middleware::Middleware;
+
+        let (provider, mock) = ethers::providers::Provider::mocked();
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: USER_REJECTED_CODE,
+            message: "User rejected the request".to_string(),
+            data: None,
+        }));
+        let err: FrameError = provider.get_accounts().await.unwrap_err().into();
+        assert!(err.is_user_rejected());
+
+        let err: FrameError = provider.get_accounts().await.unwrap_err().into();
+        assert!(matches!(err, FrameError::ProviderError(_)));
+    }
+}
@@ -0,0 +1,279 @@
+use crate::{
+    client::FrameClient,
+    error::{bail, FrameError, Result},
+    types::FeeOverrides,
+};
+use ethers::{
+    middleware::Middleware,
+    providers::JsonRpcClient,
+    types::{BlockNumber, FeeHistory, U256},
+};
+
+/// The number of recent blocks [`FrameClient::suggest_fees`] looks at.
+const FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// The percentile of priority fees paid in each block used by [`FrameClient::suggest_fees`].
+const PRIORITY_FEE_PERCENTILE: f64 = 50.0;
+
+/// Computes `(max_fee_per_gas, max_priority_fee_per_gas)` from a fee history requested with a
+/// single reward percentile.
+///
+/// The priority fee is the median of the per-block rewards and the max fee leaves room for the
+/// base fee to double before the transaction is included.
+fn fees_from_history(history: &FeeHistory) -> Result<(U256, U256)> {
+    // `base_fee_per_gas` has one more entry than the range: the next block's base fee.
+    let Some(&base_fee) = history.base_fee_per_gas.last() else {
+        bail!("Fee history has no base fee");
+    };
+
+    let mut rewards: Vec<U256> = history
+        .reward
+        .iter()
+        .filter_map(|rewards| rewards.first().copied())
+        .collect();
+    rewards.sort();
+    let priority_fee = rewards.get(rewards.len() / 2).copied().unwrap_or_default();
+
+    Ok((base_fee * 2 + priority_fee, priority_fee))
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Retrieves the current gas price of the connected network with `eth_gasPrice`.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the gas price in Wei.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let gas_price = client.get_gas_price().await?;
+    ///     println!("Gas price: {} wei", gas_price);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the gas price cannot be fetched from the connected provider.
+    pub async fn get_gas_price(&self) -> Result<U256> {
+        let gas_price = self.provider.get_gas_price().await?;
+        Ok(gas_price)
+    }
+
+    /// Retrieves the base fees and priority fee rewards of recent blocks with `eth_feeHistory`.
+    ///
+    /// # Parameters
+    /// - `block_count`: The number of blocks to return, ending at `newest_block`.
+    /// - `newest_block`: The newest block of the range, e.g. `BlockNumber::Latest`.
+    /// - `reward_percentiles`: Increasing percentiles (0 to 100) of the priority fees paid in
+    ///   each block to return.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `FeeHistory` of the range.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{BlockNumber, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let history = client
+    ///         .get_fee_history(10, BlockNumber::Latest, &[25.0, 50.0, 75.0])
+    ///         .await?;
+    ///     println!("Base fees: {:?}", history.base_fee_per_gas);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the fee history cannot be fetched from the connected provider, for
+    /// example on a network without EIP-1559.
+    pub async fn get_fee_history(
+        &self,
+        block_count: u64,
+        newest_block: BlockNumber,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory> {
+        let history = self
+            .provider
+            .fee_history(block_count, newest_block, reward_percentiles)
+            .await?;
+        Ok(history)
+    }
+
+    /// Suggests EIP-1559 fee caps for a transaction included in the next few blocks.
+    ///
+    /// The suggestion is computed from the recent fee history with the same estimator as
+    /// ethers' `Middleware::estimate_eip1559_fees`. The result can be used directly as a
+    /// `GasStrategy::Eip1559`.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps `(max_fee_per_gas, max_priority_fee_per_gas)`
+    /// in Wei.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::{client::FrameClient, types::GasStrategy};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let (max_fee, priority_fee) = client.suggest_eip1559_fees().await?;
+    ///     let strategy = GasStrategy::Eip1559 { max_fee, priority_fee };
+    ///     client
+    ///         .send_gas_token_with_strategy(from, to, U256::exp10(18), strategy)
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the fee history or latest block cannot be fetched, for example on a
+    /// network without EIP-1559.
+    pub async fn suggest_eip1559_fees(&self) -> Result<(U256, U256)> {
+        let fees = self.provider.estimate_eip1559_fees(None).await?;
+        Ok(fees)
+    }
+
+    /// Retrieves the node's suggested priority fee (tip) per gas with `eth_maxPriorityFeePerGas`.
+    ///
+    /// # Errors
+    /// Returns an error if the node does not support the method or the request fails.
+    pub async fn get_max_priority_fee(&self) -> Result<U256> {
+        let fee = self
+            .provider
+            .request("eth_maxPriorityFeePerGas", ())
+            .await?;
+        Ok(fee)
+    }
+
+    /// Retrieves the blob base fee of the next block with `eth_blobBaseFee` (EIP-4844).
+    ///
+    /// # Errors
+    /// Returns `FrameError::Unsupported` if the node does not implement the method, e.g. on a
+    /// chain without blob transactions, and an error if the request fails.
+    pub async fn get_blob_base_fee(&self) -> Result<U256> {
+        match self.provider.request("eth_blobBaseFee", ()).await {
+            Ok(fee) => Ok(fee),
+            Err(err) => match FrameError::from(err) {
+                err if err.is_method_not_found() => {
+                    Err(FrameError::Unsupported("eth_blobBaseFee".to_string()))
+                }
+                err => Err(err),
+            },
+        }
+    }
+
+    /// Estimates EIP-1559 fee caps from the latest block's base fee and the node's suggested
+    /// priority fee. The max fee is twice the base fee plus the priority fee.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `FeeOverrides`, or `None` if the latest
+    /// block has no base fee because the network does not support EIP-1559.
+    ///
+    /// # Errors
+    /// Returns an error if the latest block or the priority fee cannot be fetched.
+    pub async fn estimate_fee_overrides(&self) -> Result<Option<FeeOverrides>> {
+        let Some(base_fee) = self.get_latest_block().await?.base_fee_per_gas else {
+            return Ok(None);
+        };
+        let priority_fee = self.get_max_priority_fee().await?;
+        Ok(Some(FeeOverrides::from_base_fee(base_fee, priority_fee)))
+    }
+
+    /// Suggests EIP-1559 fee caps from the fee history of the last 10 blocks.
+    ///
+    /// The priority fee is the median of the 50th percentile priority fee paid in each block,
+    /// and the max fee is twice the next block's base fee plus that priority fee. Compared to
+    /// [`FrameClient::suggest_eip1559_fees`], this needs a single `eth_feeHistory` request.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps `(max_fee_per_gas, max_priority_fee_per_gas)`
+    /// in Wei.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let (max_fee, priority_fee) = client.suggest_fees().await?;
+    ///     client
+    ///         .send_gas_token_1559(from, to, U256::exp10(18), max_fee, priority_fee)
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the fee history cannot be fetched or has no base fee, for example on
+    /// a network without EIP-1559.
+    pub async fn suggest_fees(&self) -> Result<(U256, U256)> {
+        let history = self
+            .get_fee_history(
+                FEE_HISTORY_BLOCKS,
+                BlockNumber::Latest,
+                &[PRIORITY_FEE_PERCENTILE],
+            )
+            .await?;
+        fees_from_history(&history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_get_fee_history() {
+        let (client, mock) = FrameClient::mocked();
+        let history: FeeHistory = serde_json::from_value(json!({
+            "baseFeePerGas": ["0x3b9aca00", "0x3b9aca01"],
+            "gasUsedRatio": [0.5],
+            "oldestBlock": "0x10",
+            "reward": [["0x1"]],
+        }))
+        .unwrap();
+        mock.push(history).unwrap();
+
+        let history = client
+            .get_fee_history(1, BlockNumber::Latest, &[50.0])
+            .await
+            .unwrap();
+        assert_eq!(history.oldest_block, U256::from(16));
+        assert_eq!(history.base_fee_per_gas[1], U256::from(1_000_000_001));
+        mock.assert_request("eth_feeHistory", ("0x1", "latest", [50.0]))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_fees_from_history() {
+        let history: FeeHistory = serde_json::from_value(json!({
+            "baseFeePerGas": ["0x64", "0x6e", "0x78", "0x82"],
+            "gasUsedRatio": [0.5, 0.9, 0.7],
+            "oldestBlock": "0x10",
+            "reward": [["0x5"], ["0x1"], ["0x3"]],
+        }))
+        .unwrap();
+
+        let (max_fee, priority_fee) = fees_from_history(&history).unwrap();
+        assert_eq!(priority_fee, U256::from(3));
+        assert_eq!(max_fee, U256::from(130 * 2 + 3));
+    }
+}
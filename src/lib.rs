@@ -1 +1,32 @@
+mod abi;
+pub mod access_list;
+pub mod approval;
+pub mod batch;
+pub mod blob;
+pub mod builder;
+pub mod chain;
 pub mod client;
+pub mod deploy;
+pub mod eip3009;
+pub mod eip712;
+pub mod ens;
+pub mod erc1155;
+pub mod erc20;
+pub mod erc721;
+pub mod error;
+pub mod fees;
+pub mod logs;
+pub mod middleware;
+pub mod multicall;
+pub mod nonce;
+pub mod pending;
+pub mod policy;
+pub mod replacement;
+pub mod safe;
+pub mod sig;
+pub mod simulate;
+pub mod siwe;
+pub mod subscriptions;
+pub mod sweep;
+pub mod transport;
+pub mod types;
@@ -0,0 +1,270 @@
+use crate::{
+    client::FrameClient,
+    error::{ensure, Result},
+};
+use ethers::{
+    middleware::Middleware,
+    providers::JsonRpcClient,
+    types::{Address, Filter, Log, H256},
+    utils::keccak256,
+};
+
+/// A filter for [`FrameClient::get_logs`], built with [`LogFilter::builder`].
+///
+/// It converts into an `ethers::types::Filter`, so it can also be used with the provider
+/// directly, and an existing `Filter` converts into a `LogFilter` as is, without the builder's
+/// validation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogFilter {
+    filter: Filter,
+}
+
+impl LogFilter {
+    /// Returns a builder for a new `LogFilter`.
+    pub fn builder() -> LogFilterBuilder {
+        LogFilterBuilder::default()
+    }
+
+    /// Returns the underlying `ethers::types::Filter`.
+    pub fn as_filter(&self) -> &Filter {
+        &self.filter
+    }
+}
+
+impl From<LogFilter> for Filter {
+    fn from(filter: LogFilter) -> Self {
+        filter.filter
+    }
+}
+
+impl From<Filter> for LogFilter {
+    fn from(filter: Filter) -> Self {
+        Self { filter }
+    }
+}
+
+/// A builder for [`LogFilter`].
+///
+/// Every field is optional. Without a block range the node applies its defaults, usually the
+/// latest block only.
+#[derive(Debug, Clone, Default)]
+pub struct LogFilterBuilder {
+    from_block: Option<u64>,
+    to_block: Option<u64>,
+    block_hash: Option<H256>,
+    addresses: Vec<Address>,
+    topics: [Option<H256>; 4],
+}
+
+impl LogFilterBuilder {
+    /// Sets the first block to include, inclusive.
+    pub fn from_block(mut self, block: u64) -> Self {
+        self.from_block = Some(block);
+        self
+    }
+
+    /// Sets the last block to include, inclusive.
+    pub fn to_block(mut self, block: u64) -> Self {
+        self.to_block = Some(block);
+        self
+    }
+
+    /// Restricts the filter to the block with the given hash, instead of a block range.
+    pub fn block_hash(mut self, hash: H256) -> Self {
+        self.block_hash = Some(hash);
+        self
+    }
+
+    /// Adds a contract address to match. Logs from any of the added addresses are returned.
+    pub fn address(mut self, address: Address) -> Self {
+        self.addresses.push(address);
+        self
+    }
+
+    /// Sets the event signature hash to match, i.e. `topic0`.
+    pub fn event_signature(mut self, signature: H256) -> Self {
+        self.topics[0] = Some(signature);
+        self
+    }
+
+    /// Sets the event to match by its signature, e.g. `Transfer(address,address,uint256)`.
+    pub fn event(self, signature: &str) -> Self {
+        self.event_signature(H256(keccak256(signature)))
+    }
+
+    /// Sets the value to match for the first indexed parameter (`topic1`).
+    pub fn topic1(mut self, topic: H256) -> Self {
+        self.topics[1] = Some(topic);
+        self
+    }
+
+    /// Sets the value to match for the second indexed parameter (`topic2`).
+    pub fn topic2(mut self, topic: H256) -> Self {
+        self.topics[2] = Some(topic);
+        self
+    }
+
+    /// Sets the value to match for the third indexed parameter (`topic3`).
+    pub fn topic3(mut self, topic: H256) -> Self {
+        self.topics[3] = Some(topic);
+        self
+    }
+
+    /// Builds the `LogFilter`.
+    ///
+    /// # Errors
+    /// Returns an error if `from_block` is after `to_block`, or if a block hash is combined
+    /// with a block range.
+    pub fn build(self) -> Result<LogFilter> {
+        if let (Some(from), Some(to)) = (self.from_block, self.to_block) {
+            ensure!(from <= to, "from_block {} is after to_block {}", from, to);
+        }
+        ensure!(
+            self.block_hash.is_none() || (self.from_block.is_none() && self.to_block.is_none()),
+            "A log filter can't have both a block hash and a block range"
+        );
+
+        let mut filter = Filter::new();
+        if let Some(hash) = self.block_hash {
+            filter = filter.at_block_hash(hash);
+        }
+        if let Some(from) = self.from_block {
+            filter = filter.from_block(from);
+        }
+        if let Some(to) = self.to_block {
+            filter = filter.to_block(to);
+        }
+        if !self.addresses.is_empty() {
+            filter = filter.address(self.addresses);
+        }
+        let [topic0, topic1, topic2, topic3] = self.topics;
+        if let Some(topic) = topic0 {
+            filter = filter.topic0(topic);
+        }
+        if let Some(topic) = topic1 {
+            filter = filter.topic1(topic);
+        }
+        if let Some(topic) = topic2 {
+            filter = filter.topic2(topic);
+        }
+        if let Some(topic) = topic3 {
+            filter = filter.topic3(topic);
+        }
+        Ok(LogFilter { filter })
+    }
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Retrieves the logs matching a filter with `eth_getLogs`.
+    ///
+    /// # Parameters
+    /// - `filter`: The `LogFilter` selecting the block range, contract addresses and topics. An
+    ///   ethers `Filter` can be passed as `&filter.into()`.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the matching logs in chain order.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::{client::FrameClient, logs::LogFilter};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse()?;
+    ///
+    ///     let filter = LogFilter::builder()
+    ///         .from_block(19_000_000)
+    ///         .to_block(19_000_100)
+    ///         .address(usdc)
+    ///         .event("Transfer(address,address,uint256)")
+    ///         .build()?;
+    ///     let logs = client.get_logs(&filter).await?;
+    ///     println!("Found {} transfers", logs.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the logs cannot be fetched from the connected provider, for example
+    /// when the node rejects a block range that is too large.
+    pub async fn get_logs(&self, filter: &LogFilter) -> Result<Vec<Log>> {
+        let logs = self.provider.get_logs(filter.as_filter()).await?;
+        Ok(logs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::U64;
+
+    #[test]
+    fn test_build_log_filter() {
+        let token = Address::random();
+        let from = H256::from(Address::random());
+        let filter = LogFilter::builder()
+            .from_block(100)
+            .to_block(200)
+            .address(token)
+            .event("Transfer(address,address,uint256)")
+            .topic1(from)
+            .build()
+            .unwrap();
+
+        let expected = Filter::new()
+            .from_block(100)
+            .to_block(200)
+            .address(vec![token])
+            .event("Transfer(address,address,uint256)")
+            .topic1(from);
+        assert_eq!(Filter::from(filter), expected);
+        assert_eq!(LogFilter::from(expected.clone()).as_filter(), &expected);
+
+        assert!(LogFilter::builder()
+            .from_block(200)
+            .to_block(100)
+            .build()
+            .is_err());
+        assert!(LogFilter::builder()
+            .from_block(1)
+            .block_hash(H256::random())
+            .build()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_logs() {
+        let (client, mock) = FrameClient::mocked();
+        let log = Log {
+            block_number: Some(U64::from(150)),
+            ..Default::default()
+        };
+        mock.push::<Vec<Log>, _>(vec![log.clone()]).unwrap();
+
+        let filter = LogFilter::builder().from_block(100).build().unwrap();
+        let logs = client.get_logs(&filter).await.unwrap();
+        assert_eq!(logs, vec![log]);
+        mock.assert_request("eth_getLogs", [filter.as_filter()])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_with_ethers_filter() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push::<Vec<Log>, _>(vec![]).unwrap();
+
+        let filter = Filter::new()
+            .address(Address::random())
+            .event("Transfer(address,address,uint256)")
+            .from_block(18_000_000)
+            .to_block(18_100_000);
+        assert!(client
+            .get_logs(&filter.clone().into())
+            .await
+            .unwrap()
+            .is_empty());
+        mock.assert_request("eth_getLogs", [filter]).unwrap();
+    }
+}
@@ -0,0 +1,386 @@
+use async_trait::async_trait;
+use ethers::{
+    core::rand::{thread_rng, Rng},
+    providers::HttpClientError,
+};
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::{fmt, sync::Arc, time::Duration};
+
+/// Hooks called around every JSON-RPC request sent to Frame over HTTP, e.g. for logging or
+/// metrics.
+///
+/// Middlewares are added with
+/// [`FrameClientBuilder::with_middleware`](crate::builder::FrameClientBuilder::with_middleware)
+/// and compose like layers: `before_request` is called in the order the middlewares were added
+/// and `after_response` and `on_error` in reverse order. Each of these hooks is called once per
+/// request, however often the transport retries it; retries are decided by `retry_after`.
+/// Requests sent by a WebSocket provider don't go through the middlewares.
+///
+/// # Examples
+/// ```no_run
+/// use async_trait::async_trait;
+/// use frame_rs::{client::FrameClient, middleware::FrameMiddleware};
+/// use serde_json::Value;
+/// use std::time::Duration;
+///
+/// struct Logger;
+///
+/// #[async_trait]
+/// impl FrameMiddleware for Logger {
+///     async fn before_request(&self, method: &str, params: &Value) {
+///         println!("-> {} {}", method, params);
+///     }
+///
+///     async fn after_response(&self, method: &str, _result: &Value, elapsed: Duration) {
+///         println!("<- {} in {:?}", method, elapsed);
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let client = FrameClient::builder().with_middleware(Logger).build().await?;
+///     client.get_accounts().await?;
+///     Ok(())
+/// }
+/// ```
+#[async_trait]
+pub trait FrameMiddleware: Send + Sync {
+    /// Called before the request is sent.
+    async fn before_request(&self, _method: &str, _params: &Value) {}
+
+    /// Called with the `result` of a successful response.
+    async fn after_response(&self, _method: &str, _result: &Value, _elapsed: Duration) {}
+
+    /// Called when the request fails, including with a JSON-RPC error response.
+    async fn on_error(&self, _method: &str, _error: &HttpClientError, _elapsed: Duration) {}
+
+    /// Called after attempt number `attempt` (starting at 1) of a request failed with `error`.
+    /// Returning a delay retries the request after it; the first middleware that does wins.
+    async fn retry_after(
+        &self,
+        _method: &str,
+        _error: &HttpClientError,
+        _attempt: usize,
+    ) -> Option<Duration> {
+        None
+    }
+}
+
+/// A [`FrameMiddleware`] that retries transient failures with exponential backoff.
+///
+/// Refused connections, timeouts and HTTP 429 and 503 responses are retried. Timeouts are only
+/// retried for read-only methods such as `eth_call`, `eth_chainId` and `eth_get*`: any other
+/// request, e.g. `eth_sendTransaction` or `eth_requestAccounts`, may be waiting for the user in
+/// Frame, and retrying it would prompt them twice. Other HTTP errors and JSON-RPC error
+/// responses, such as a rejection in Frame, are returned right away. The
+/// delay before retry `n` is `base_delay * 2^(n - 1)`, capped at `max_delay`; with `jitter`,
+/// a random delay between zero and that value is used instead ("full jitter").
+///
+/// # Examples
+/// ```no_run
+/// use frame_rs::{client::FrameClient, middleware::RetryMiddleware};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let client = FrameClient::builder()
+///         .with_middleware(RetryMiddleware::default())
+///         .build()
+///         .await?;
+///     client.get_accounts().await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryMiddleware {
+    /// The maximum number of attempts, including the first one.
+    pub max_attempts: usize,
+    /// The delay before the first retry.
+    pub base_delay: Duration,
+    /// The upper bound of the delay between attempts.
+    pub max_delay: Duration,
+    /// Whether to randomize each delay between zero and its exponential value.
+    pub jitter: bool,
+}
+
+impl Default for RetryMiddleware {
+    /// Four attempts, starting at 250 milliseconds and capped at 5 seconds, with jitter.
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(5),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryMiddleware {
+    /// Returns the delay before the retry that follows attempt number `attempt`, without
+    /// jitter.
+    pub fn backoff(&self, attempt: usize) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31) as u32;
+        self.base_delay
+            .saturating_mul(2u32.pow(exponent))
+            .min(self.max_delay)
+    }
+
+    /// Returns `true` if a `method` request that failed with `error` may succeed when retried
+    /// without being processed twice.
+    pub fn is_transient(method: &str, error: &HttpClientError) -> bool {
+        let HttpClientError::ReqwestError(err) = error else {
+            return false;
+        };
+        match err.status() {
+            Some(status) => {
+                status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::SERVICE_UNAVAILABLE
+            }
+            None => err.is_connect() || (err.is_timeout() && is_read_only(method)),
+        }
+    }
+}
+
+/// Returns `true` if a `method` request only reads state, so it can't open a prompt in Frame
+/// or submit a transaction and is safe to send twice.
+fn is_read_only(method: &str) -> bool {
+    method.starts_with("eth_get")
+        || matches!(
+            method,
+            "eth_accounts"
+                | "eth_blobBaseFee"
+                | "eth_blockNumber"
+                | "eth_call"
+                | "eth_chainId"
+                | "eth_createAccessList"
+                | "eth_estimateGas"
+                | "eth_feeHistory"
+                | "eth_gasPrice"
+                | "eth_maxPriorityFeePerGas"
+                | "eth_syncing"
+                | "net_version"
+                | "web3_clientVersion"
+        )
+}
+
+#[async_trait]
+impl FrameMiddleware for RetryMiddleware {
+    async fn retry_after(
+        &self,
+        method: &str,
+        error: &HttpClientError,
+        attempt: usize,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !Self::is_transient(method, error) {
+            return None;
+        }
+
+        let delay = self.backoff(attempt);
+        if !self.jitter {
+            return Some(delay);
+        }
+        let millis = delay.as_millis() as u64;
+        Some(Duration::from_millis(thread_rng().gen_range(0..=millis)))
+    }
+}
+
+/// An ordered list of [`FrameMiddleware`]s.
+#[derive(Clone, Default)]
+pub(crate) struct MiddlewareStack(Vec<Arc<dyn FrameMiddleware>>);
+
+impl MiddlewareStack {
+    pub(crate) fn push(&mut self, middleware: Arc<dyn FrameMiddleware>) {
+        self.0.push(middleware);
+    }
+
+    pub(crate) async fn before_request(&self, method: &str, params: &Value) {
+        for middleware in &self.0 {
+            middleware.before_request(method, params).await;
+        }
+    }
+
+    pub(crate) async fn after_response(&self, method: &str, result: &Value, elapsed: Duration) {
+        for middleware in self.0.iter().rev() {
+            middleware.after_response(method, result, elapsed).await;
+        }
+    }
+
+    pub(crate) async fn on_error(&self, method: &str, error: &HttpClientError, elapsed: Duration) {
+        for middleware in self.0.iter().rev() {
+            middleware.on_error(method, error, elapsed).await;
+        }
+    }
+
+    pub(crate) async fn retry_after(
+        &self,
+        method: &str,
+        error: &HttpClientError,
+        attempt: usize,
+    ) -> Option<Duration> {
+        for middleware in &self.0 {
+            if let Some(delay) = middleware.retry_after(method, error, attempt).await {
+                return Some(delay);
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Debug for MiddlewareStack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MiddlewareStack({} middlewares)", self.0.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::{serve_http, FrameClient},
+        transport::FrameTransport,
+    };
+    use ethers::providers::JsonRpcClient;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    struct Recorder {
+        name: &'static str,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl FrameMiddleware for Recorder {
+        async fn before_request(&self, method: &str, _params: &Value) {
+            let call = format!("{} before {}", self.name, method);
+            self.calls.lock().unwrap().push(call);
+        }
+
+        async fn after_response(&self, method: &str, result: &Value, _elapsed: Duration) {
+            let call = format!("{} after {} {}", self.name, method, result);
+            self.calls.lock().unwrap().push(call);
+        }
+
+        async fn on_error(&self, method: &str, _error: &HttpClientError, _elapsed: Duration) {
+            let call = format!("{} error {}", self.name, method);
+            self.calls.lock().unwrap().push(call);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middlewares_compose_in_order() {
+        let (mut client, _) = FrameClient::mocked_with_rpc(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "result": null }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": 4001, "message": "User rejected the request" }
+            }),
+        ])
+        .await;
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut middlewares = MiddlewareStack::default();
+        for name in ["outer", "inner"] {
+            let calls = calls.clone();
+            middlewares.push(Arc::new(Recorder { name, calls }));
+        }
+        client.rpc = client.rpc.clone().with_middlewares(middlewares);
+
+        client.switch_network(10.into()).await.unwrap();
+        assert!(client.switch_network(10.into()).await.is_err());
+        assert_eq!(
+            *calls.lock().unwrap(),
+            [
+                "outer before wallet_switchEthereumChain",
+                "inner before wallet_switchEthereumChain",
+                "inner after wallet_switchEthereumChain null",
+                "outer after wallet_switchEthereumChain null",
+                "outer before wallet_switchEthereumChain",
+                "inner before wallet_switchEthereumChain",
+                "inner error wallet_switchEthereumChain",
+                "outer error wallet_switchEthereumChain",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let retry = RetryMiddleware {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            jitter: false,
+        };
+        assert_eq!(retry.backoff(1), Duration::from_millis(100));
+        assert_eq!(retry.backoff(3), Duration::from_millis(400));
+        assert_eq!(retry.backoff(5), Duration::from_secs(1));
+        assert_eq!(retry.backoff(100), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_retry_middleware_retries_transient_statuses() {
+        let url = serve_http(vec![
+            (503, "Service Unavailable".to_string()),
+            (429, "Too Many Requests".to_string()),
+            (
+                200,
+                json!({ "jsonrpc": "2.0", "id": 3, "result": "0x1" }).to_string(),
+            ),
+            (400, "Bad Request".to_string()),
+        ])
+        .await;
+        let retry = RetryMiddleware {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(10),
+            jitter: true,
+        };
+        let mut middlewares = MiddlewareStack::default();
+        middlewares.push(Arc::new(retry));
+        let transport = FrameTransport::new(url.parse().unwrap(), Default::default())
+            .with_middlewares(middlewares);
+
+        let chain_id: String = transport.request("eth_chainId", ()).await.unwrap();
+        assert_eq!(chain_id, "0x1");
+
+        // A 400 response is not retried; its body isn't JSON-RPC.
+        let err = transport
+            .request::<_, String>("eth_chainId", ())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, HttpClientError::SerdeJson { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_are_transient_for_read_only_methods() {
+        // Accept the connection but never answer, so the request times out.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                sockets.push(socket);
+            }
+        });
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(20))
+            .build()
+            .unwrap();
+        let err = client.post(url).send().await.unwrap_err();
+        assert!(err.is_timeout());
+        let err = HttpClientError::ReqwestError(err);
+
+        for method in ["eth_chainId", "eth_call", "eth_getTransactionReceipt"] {
+            assert!(RetryMiddleware::is_transient(method, &err), "{}", method);
+        }
+        for method in [
+            "eth_requestAccounts",
+            "eth_sendTransaction",
+            "eth_sendRawTransaction",
+            "eth_signTransaction",
+            "eth_signTypedData_v4",
+            "personal_sign",
+            "wallet_switchEthereumChain",
+        ] {
+            assert!(!RetryMiddleware::is_transient(method, &err), "{}", method);
+        }
+    }
+}
@@ -0,0 +1,373 @@
+use crate::{
+    abi::function,
+    client::FrameClient,
+    error::{bail, FrameError, Result},
+    transport::FrameTransport,
+};
+use ethers::{
+    abi::Token,
+    middleware::Middleware,
+    providers::JsonRpcClient,
+    types::{transaction::eip2718::TypedTransaction, Address, Bytes, TransactionRequest, H160},
+};
+
+/// The address of the Multicall3 contract, `0xcA11bde05977b3631167028862bE2a173976CA11`, the
+/// same on most chains.
+pub const MULTICALL3_ADDRESS: Address = H160([
+    0xca, 0x11, 0xbd, 0xe0, 0x59, 0x77, 0xb3, 0x63, 0x11, 0x67, 0x02, 0x88, 0x62, 0xbe, 0x2a, 0x17,
+    0x39, 0x76, 0xca, 0x11,
+]);
+
+/// `aggregate3` takes `(target, allowFailure, callData)` tuples and returns
+/// `(success, returnData)` tuples.
+const AGGREGATE3: &str =
+    "function aggregate3((address,bool,bytes)[] calls) payable returns ((bool,bytes)[] returnData)";
+
+/// The outcome of one call of a [`Multicall3Client`] batch.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CallResult {
+    /// Whether the call succeeded. Only calls added with `allow_failure` can fail without
+    /// failing the whole batch.
+    pub success: bool,
+    /// The return data of the call, or its revert data if it failed.
+    pub return_data: Bytes,
+}
+
+/// Batches read-only calls to several contracts into a single `eth_call` through the
+/// Multicall3 contract's `aggregate3`.
+///
+/// Calls are added with [`Multicall3Client::add_call`] and executed together, in order and
+/// against the same block, with [`Multicall3Client::execute`]. The Multicall3 address is the
+/// wrapped client's, [`MULTICALL3_ADDRESS`] unless it was changed with
+/// [`FrameClientBuilder::multicall_address`](crate::builder::FrameClientBuilder::multicall_address).
+///
+/// # Examples
+/// ```no_run
+/// use ethers::{
+///     abi::{encode, Token},
+///     types::{Address, Bytes, U256},
+///     utils::id,
+/// };
+/// use frame_rs::{client::FrameClient, multicall::Multicall3Client};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let client = FrameClient::new(U256::from(1), None, None).await?;
+///     let owner: Address = "0x...".parse()?;
+///     let tokens: Vec<Address> = vec!["0x...".parse()?, "0x...".parse()?];
+///
+///     let mut data = id("balanceOf(address)").to_vec();
+///     data.extend(encode(&[Token::Address(owner)]));
+///     let mut multicall = Multicall3Client::new(client);
+///     for token in &tokens {
+///         multicall.add_call(*token, Bytes::from(data.clone()), true);
+///     }
+///
+///     for (token, result) in tokens.iter().zip(multicall.execute().await?) {
+///         match result.success {
+///             true => println!("{:?}: {}", token, U256::from_big_endian(&result.return_data)),
+///             false => println!("{:?}: call failed", token),
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Multicall3Client<P = FrameTransport> {
+    client: FrameClient<P>,
+    calls: Vec<(Address, bool, Bytes)>,
+}
+
+impl<P: JsonRpcClient> Multicall3Client<P> {
+    /// Creates an empty batch of calls executed through `client`.
+    pub fn new(client: FrameClient<P>) -> Self {
+        Self {
+            client,
+            calls: Vec::new(),
+        }
+    }
+
+    /// Returns the wrapped client.
+    pub fn client(&self) -> &FrameClient<P> {
+        &self.client
+    }
+
+    /// Adds a call of `calldata` to `target` to the batch. If `allow_failure` is `false`, a
+    /// revert of this call reverts the whole batch.
+    pub fn add_call(&mut self, target: Address, calldata: Bytes, allow_failure: bool) -> &mut Self {
+        self.calls.push((target, allow_failure, calldata));
+        self
+    }
+
+    /// Returns the number of calls in the batch.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns `true` if no calls were added.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Removes all calls from the batch.
+    pub fn clear(&mut self) {
+        self.calls.clear();
+    }
+
+    /// Executes the calls of the batch in a single `eth_call` with `aggregate3`. The calls are
+    /// kept, so the batch can be executed again, e.g. at a later block.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps a `CallResult` for each call, in the order the
+    /// calls were added.
+    ///
+    /// # Errors
+    /// Returns `FrameError::ExecutionReverted` if a call that doesn't allow failure reverts,
+    /// and an error if Multicall3 is not deployed at the configured address or its output
+    /// cannot be decoded.
+    pub async fn execute(&self) -> Result<Vec<CallResult>> {
+        self.client.aggregate3(self.calls.clone()).await
+    }
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Performs several read-only calls in a single `eth_call` through the Multicall3
+    /// contract.
+    ///
+    /// The calls are executed in order against the same block, with `aggregate3`. The
+    /// Multicall3 address defaults to [`MULTICALL3_ADDRESS`] and can be changed with
+    /// [`FrameClientBuilder::multicall_address`](crate::builder::FrameClientBuilder::multicall_address)
+    /// for chains that deploy it elsewhere.
+    ///
+    /// # Parameters
+    /// - `calls`: The contract address and calldata of each call.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the raw return data of each call, in the
+    /// order of `calls`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::{
+    ///     abi::{encode, Token},
+    ///     types::{Address, Bytes, U256},
+    ///     utils::id,
+    /// };
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let owner: Address = "0x...".parse()?;
+    ///     let tokens: Vec<Address> = vec!["0x...".parse()?, "0x...".parse()?];
+    ///
+    ///     let mut data = id("balanceOf(address)").to_vec();
+    ///     data.extend(encode(&[Token::Address(owner)]));
+    ///     let calls = tokens
+    ///         .iter()
+    ///         .map(|token| (*token, Bytes::from(data.clone())))
+    ///         .collect();
+    ///
+    ///     for (token, output) in tokens.iter().zip(client.multicall(calls).await?) {
+    ///         println!("{:?}: {}", token, U256::from_big_endian(&output));
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `FrameError::ExecutionReverted` if any of the calls reverts, and an error if
+    /// Multicall3 is not deployed at the configured address or its output cannot be decoded.
+    pub async fn multicall(&self, calls: Vec<(Address, Bytes)>) -> Result<Vec<Bytes>> {
+        let calls = calls
+            .into_iter()
+            .map(|(target, data)| (target, false, data))
+            .collect();
+        let results = self.aggregate3(calls).await?;
+        Ok(results
+            .into_iter()
+            .map(|result| result.return_data)
+            .collect())
+    }
+
+    /// Executes `(target, allow_failure, calldata)` calls with `aggregate3`.
+    async fn aggregate3(&self, calls: Vec<(Address, bool, Bytes)>) -> Result<Vec<CallResult>> {
+        if calls.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let function = function(AGGREGATE3);
+        let calls = calls
+            .into_iter()
+            .map(|(target, allow_failure, data)| {
+                Token::Tuple(vec![
+                    Token::Address(target),
+                    Token::Bool(allow_failure),
+                    Token::Bytes(data.to_vec()),
+                ])
+            })
+            .collect();
+        let data = function.encode_input(&[Token::Array(calls)])?;
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(self.multicall_address)
+            .data(data)
+            .into();
+
+        let output = self
+            .provider
+            .call(&tx, None)
+            .await
+            .map_err(FrameError::from_revert)?;
+        if output.is_empty() {
+            bail!("No Multicall3 contract at {:?}", self.multicall_address);
+        }
+        let Some(Token::Array(results)) = function.decode_output(&output)?.pop() else {
+            bail!("Unexpected aggregate3 output: {}", output);
+        };
+        results
+            .into_iter()
+            .map(|result| match result {
+                Token::Tuple(fields) => match fields.as_slice() {
+                    [Token::Bool(success), Token::Bytes(data)] => Ok(CallResult {
+                        success: *success,
+                        return_data: Bytes::from(data.clone()),
+                    }),
+                    _ => bail!("Unexpected aggregate3 result: {:?}", fields),
+                },
+                token => bail!("Unexpected aggregate3 result: {:?}", token),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        abi::encode,
+        providers::{JsonRpcError, MockResponse},
+        types::U256,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn test_multicall3_address() {
+        let address: Address = "0xcA11bde05977b3631167028862bE2a173976CA11"
+            .parse()
+            .unwrap();
+        assert_eq!(MULTICALL3_ADDRESS, address);
+    }
+
+    #[tokio::test]
+    async fn test_multicall() {
+        let (mut client, mock) = FrameClient::mocked();
+        client.multicall_address = Address::random();
+        let (first, second) = (Address::random(), Address::random());
+        let balance = encode(&[Token::Uint(U256::from(42))]);
+        let output = encode(&[Token::Array(vec![
+            Token::Tuple(vec![Token::Bool(true), Token::Bytes(balance.clone())]),
+            Token::Tuple(vec![Token::Bool(true), Token::Bytes(Vec::new())]),
+        ])]);
+        mock.push::<Bytes, _>(Bytes::from(output)).unwrap();
+
+        let calls = vec![
+            (first, Bytes::from_static(&[0x70, 0xa0, 0x82, 0x31])),
+            (second, Bytes::new()),
+        ];
+        let results = client.multicall(calls).await.unwrap();
+        assert_eq!(results, [Bytes::from(balance), Bytes::new()]);
+
+        let data = function(AGGREGATE3)
+            .encode_input(&[Token::Array(vec![
+                Token::Tuple(vec![
+                    Token::Address(first),
+                    Token::Bool(false),
+                    Token::Bytes(vec![0x70, 0xa0, 0x82, 0x31]),
+                ]),
+                Token::Tuple(vec![
+                    Token::Address(second),
+                    Token::Bool(false),
+                    Token::Bytes(Vec::new()),
+                ]),
+            ])])
+            .unwrap();
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(client.multicall_address)
+            .data(data)
+            .into();
+        mock.assert_request("eth_call", (tx, "latest")).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_multicall3_client() {
+        let (client, mock) = FrameClient::mocked();
+        let (first, second) = (Address::random(), Address::random());
+        let revert = Bytes::from_static(&[0x4e, 0x48, 0x7b, 0x71]);
+        let output = encode(&[Token::Array(vec![
+            Token::Tuple(vec![Token::Bool(true), Token::Bytes(vec![0x2a])]),
+            Token::Tuple(vec![Token::Bool(false), Token::Bytes(revert.to_vec())]),
+        ])]);
+        mock.push::<Bytes, _>(Bytes::from(output)).unwrap();
+
+        let mut multicall = Multicall3Client::new(client);
+        assert!(multicall.execute().await.unwrap().is_empty());
+        multicall
+            .add_call(first, Bytes::from_static(&[0x01]), false)
+            .add_call(second, Bytes::new(), true);
+        assert_eq!(multicall.len(), 2);
+        assert_eq!(
+            multicall.execute().await.unwrap(),
+            [
+                CallResult {
+                    success: true,
+                    return_data: Bytes::from_static(&[0x2a]),
+                },
+                CallResult {
+                    success: false,
+                    return_data: revert,
+                },
+            ]
+        );
+
+        let data = function(AGGREGATE3)
+            .encode_input(&[Token::Array(vec![
+                Token::Tuple(vec![
+                    Token::Address(first),
+                    Token::Bool(false),
+                    Token::Bytes(vec![0x01]),
+                ]),
+                Token::Tuple(vec![
+                    Token::Address(second),
+                    Token::Bool(true),
+                    Token::Bytes(Vec::new()),
+                ]),
+            ])])
+            .unwrap();
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(MULTICALL3_ADDRESS)
+            .data(data)
+            .into();
+        mock.assert_request("eth_call", (tx, "latest")).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_multicall_errors() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push::<Bytes, _>(Bytes::new()).unwrap();
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted: Multicall3: call failed".to_string(),
+            data: Some(json!("0x")),
+        }));
+        let calls = vec![(Address::random(), Bytes::new())];
+
+        let err = client.multicall(calls.clone()).await.unwrap_err();
+        assert!(matches!(
+            err,
+            FrameError::ExecutionReverted { reason, .. } if reason == "Multicall3: call failed"
+        ));
+        let err = client.multicall(calls).await.unwrap_err();
+        assert!(err.to_string().starts_with("No Multicall3 contract at"));
+        assert!(client.multicall(Vec::new()).await.unwrap().is_empty());
+    }
+}
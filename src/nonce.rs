@@ -0,0 +1,304 @@
+use crate::{
+    client::FrameClient,
+    error::{bail, Result},
+    transport::FrameTransport,
+};
+use ethers::{
+    providers::JsonRpcClient,
+    types::{Address, TransactionRequest, H256, U256},
+};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+
+/// The next nonce of each account on each chain, shared by the clones of a client.
+#[derive(Clone, Default)]
+pub(crate) struct NonceCache(Arc<Mutex<HashMap<(Address, U256), U256>>>);
+
+/// Assigns nonces locally to transactions sent through a [`FrameClient`], so several
+/// transactions from the same account can be sent in quick succession without colliding.
+///
+/// This enables the client's nonce management, as
+/// [`FrameClientBuilder::manage_nonces`](crate::builder::FrameClientBuilder::manage_nonces)
+/// does, and gives direct access to the reserved nonces. The first nonce of each account on
+/// each chain is its pending transaction count. After that, each send takes the next nonce
+/// from the cache. If a send fails before it reaches Frame or Frame refuses it, for example
+/// because the user rejected it, its nonce is released and used by the next send. A send that
+/// times out keeps its nonce, since the transaction may still be approved in Frame; call
+/// [`FrameClient::sync_nonces`] once it is known not to have been sent. Clones share the same
+/// cache.
+///
+/// # Examples
+/// ```no_run
+/// use ethers::types::{Address, U256};
+/// use frame_rs::{client::FrameClient, nonce::NonceManager};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let client = FrameClient::new(U256::from(1), None, None).await?;
+///     let nonces = NonceManager::new(client);
+///     let from: Address = "0x...".parse()?;
+///     let recipients: Vec<Address> = vec!["0x...".parse()?, "0x...".parse()?];
+///
+///     for to in recipients {
+///         nonces.send_gas_token(from, to, U256::exp10(16)).await?;
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct NonceManager<P = FrameTransport> {
+    client: FrameClient<P>,
+}
+
+impl<P: JsonRpcClient> NonceManager<P> {
+    /// Creates a nonce manager for transactions sent through `client`, enabling its nonce
+    /// management if it isn't already.
+    pub fn new(mut client: FrameClient<P>) -> Self {
+        client.nonces.get_or_insert_with(NonceCache::default);
+        Self { client }
+    }
+
+    /// Returns the wrapped client.
+    pub fn client(&self) -> &FrameClient<P> {
+        &self.client
+    }
+
+    /// Reserves the next nonce of `address` on the connected chain, querying the pending
+    /// transaction count if none is cached.
+    ///
+    /// # Errors
+    /// Returns an error if the chain ID or the transaction count cannot be fetched.
+    pub async fn next_nonce(&self, address: Address) -> Result<U256> {
+        let (_, nonce) = self.client.reserve_nonce(self.cache(), address).await?;
+        Ok(nonce)
+    }
+
+    /// Discards the cached nonce of `address` and queries its pending transaction count again.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the nonce the next transaction will use.
+    ///
+    /// # Errors
+    /// Returns an error if the chain ID or the transaction count cannot be fetched.
+    pub async fn reset_nonce(&self, address: Address) -> Result<U256> {
+        let mut next = self.cache().0.lock().await;
+        let chain_id = self.client.get_chain_id().await?;
+        let nonce = self.client.get_pending_nonce(address).await?;
+        next.insert((address, chain_id), nonce);
+        Ok(nonce)
+    }
+
+    /// Sends the native gas token like [`FrameClient::send_gas_token`], with the next nonce
+    /// of `from`.
+    ///
+    /// # Errors
+    /// Returns an error if the nonce cannot be fetched, if the transaction fails to be sent or
+    /// if no receipt is returned for it.
+    pub async fn send_gas_token(&self, from: Address, to: Address, amount: U256) -> Result<H256> {
+        let tx = TransactionRequest::new().from(from).to(to).value(amount);
+        self.send_transaction(tx).await
+    }
+
+    /// Sends a transaction like [`FrameClient::send_transaction`], with the next nonce of its
+    /// `from` address. A nonce already set on `tx` is replaced.
+    ///
+    /// # Errors
+    /// Returns an error if `tx` has no `from` address, if the nonce cannot be fetched, if the
+    /// transaction fails to be sent or if no receipt is returned for it.
+    pub async fn send_transaction(&self, mut tx: TransactionRequest) -> Result<H256> {
+        if tx.from.is_none() {
+            bail!("Cannot assign a nonce to a transaction without a `from` address");
+        }
+        tx.nonce = None;
+        self.client.send_transaction(tx).await
+    }
+
+    fn cache(&self) -> &NonceCache {
+        self.client
+            .nonces
+            .as_ref()
+            .expect("NonceManager::new enables nonce management")
+    }
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Forgets the nonces assigned by the client's nonce management, so the next transaction
+    /// of each account starts again from its pending transaction count.
+    ///
+    /// Call this after transactions were sent from the Frame UI directly or by another
+    /// application, which the client can't know about. Switching networks with
+    /// [`FrameClient::switch_network`] does this automatically. Without nonce management,
+    /// enabled with
+    /// [`FrameClientBuilder::manage_nonces`](crate::builder::FrameClientBuilder::manage_nonces),
+    /// this does nothing.
+    pub async fn sync_nonces(&self) {
+        if let Some(nonces) = &self.nonces {
+            nonces.0.lock().await.clear();
+        }
+    }
+
+    /// Reserves the next nonce of `from` on the connected chain, returning the cache key with
+    /// it.
+    pub(crate) async fn reserve_nonce(
+        &self,
+        nonces: &NonceCache,
+        from: Address,
+    ) -> Result<((Address, U256), U256)> {
+        let mut next = nonces.0.lock().await;
+        let key = (from, self.get_chain_id().await?);
+        let nonce = match next.get(&key) {
+            Some(nonce) => *nonce,
+            None => self.get_pending_nonce(from).await?,
+        };
+        next.insert(key, nonce + 1);
+        Ok((key, nonce))
+    }
+}
+
+impl NonceCache {
+    /// Returns a nonce reserved for a transaction that was not broadcast. If later nonces were
+    /// reserved meanwhile, the account's entry is discarded instead, so that it is queried
+    /// again rather than left with a gap.
+    pub(crate) async fn release(&self, key: (Address, U256), nonce: U256) {
+        let mut next = self.0.lock().await;
+        if next.get(&key) == Some(&(nonce + 1)) {
+            next.insert(key, nonce);
+        } else {
+            next.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        providers::{JsonRpcError, MockResponse},
+        types::transaction::eip2718::TypedTransaction,
+    };
+
+    #[tokio::test]
+    async fn test_next_nonce_and_reset() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push(U256::from(1)).unwrap();
+        mock.push(U256::from(9)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        mock.push(U256::from(5)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        let nonces = NonceManager::new(client);
+        let address = Address::random();
+
+        assert_eq!(nonces.next_nonce(address).await.unwrap(), U256::from(5));
+        assert_eq!(nonces.next_nonce(address).await.unwrap(), U256::from(6));
+        assert_eq!(nonces.reset_nonce(address).await.unwrap(), U256::from(9));
+        assert_eq!(nonces.next_nonce(address).await.unwrap(), U256::from(9));
+        mock.assert_request("eth_chainId", ()).unwrap();
+        mock.assert_request("eth_getTransactionCount", (address, "pending"))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_send_releases_nonce() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push(U256::from(3)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        let nonces = NonceManager::new(client);
+        let from = Address::random();
+
+        // The mock has no response for the send, so it fails after the nonce is reserved.
+        assert!(nonces
+            .send_gas_token(from, Address::random(), U256::from(1))
+            .await
+            .is_err());
+        mock.push(U256::from(1)).unwrap();
+        assert_eq!(nonces.next_nonce(from).await.unwrap(), U256::from(3));
+    }
+
+    #[tokio::test]
+    async fn test_unanswered_send_keeps_nonce() {
+        let (client, mock) = FrameClient::mocked();
+        let nonces = NonceManager::new(client);
+        let from = Address::random();
+
+        // The user rejects the first send, so its nonce is reused.
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 4001,
+            message: "User rejected the request".to_string(),
+            data: None,
+        }));
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        mock.push(U256::from(3)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        let err = nonces
+            .send_gas_token(from, Address::random(), U256::from(1))
+            .await
+            .unwrap_err();
+        assert!(err.is_user_rejected());
+
+        // The second send gets no answer, as if it timed out, so it may still be pending in
+        // Frame and its nonce isn't reused.
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        assert!(nonces
+            .send_gas_token(from, Address::random(), U256::from(1))
+            .await
+            .is_err());
+        mock.push(U256::from(1)).unwrap();
+        assert_eq!(nonces.next_nonce(from).await.unwrap(), U256::from(4));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_sends_get_distinct_nonces() {
+        let (mut client, mock) = FrameClient::mocked();
+        client.nonces = Some(NonceCache::default());
+        let (from, to) = (Address::random(), Address::random());
+        const SENDS: u64 = 8;
+
+        // Sends are serialized by the prompt queue, so each one consumes its responses in turn.
+        for send in (0..SENDS).rev() {
+            mock.push(H256::random()).unwrap();
+            mock.push(U256::from(21_000)).unwrap();
+            mock.push(U256::from(1)).unwrap();
+            if send == 0 {
+                mock.push(U256::from(7)).unwrap();
+            }
+            mock.push(U256::from(1)).unwrap();
+        }
+
+        let tasks: Vec<_> = (0..SENDS)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move {
+                    let sent = client.send_gas_token_pending(from, to, U256::one());
+                    sent.await.map(|pending| pending.hash())
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        for nonce in 7..7 + SENDS {
+            let estimated: TypedTransaction = TransactionRequest::new()
+                .from(from)
+                .to(to)
+                .value(1)
+                .nonce(nonce)
+                .gas_price(1)
+                .into();
+            let mut sent = estimated.clone();
+            sent.set_gas(21_000);
+            mock.assert_request("eth_chainId", ()).unwrap();
+            if nonce == 7 {
+                mock.assert_request("eth_getTransactionCount", (from, "pending"))
+                    .unwrap();
+            }
+            mock.assert_request("eth_gasPrice", ()).unwrap();
+            mock.assert_request("eth_estimateGas", [estimated]).unwrap();
+            mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+        }
+    }
+}
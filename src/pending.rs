@@ -0,0 +1,628 @@
+use crate::{
+    chain::{confirmed_receipt, replay_revert_reason},
+    client::FrameClient,
+    error::{FrameError, Result},
+    nonce::NonceCache,
+};
+use ethers::{
+    middleware::Middleware,
+    providers::JsonRpcClient,
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Transaction, TransactionReceipt,
+        TransactionRequest, H256, U256,
+    },
+};
+use std::{
+    future::{Future, IntoFuture},
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use tokio::sync::MutexGuard;
+
+/// The default number of polls in a row that must find no transaction, after it was seen, before
+/// a pending transaction is considered dropped from the mempool.
+pub const DEFAULT_DROPPED_AFTER_POLLS: usize = 4;
+
+/// How often a dropped transaction is resubmitted before `FrameError::TransactionDropped` is
+/// returned, with automatic resubmission enabled.
+pub const MAX_RESUBMISSIONS: usize = 3;
+
+/// A send prepared by [`FrameClient::prepare_send`], holding the prompt in Frame and the nonce
+/// reserved for the transaction, if any.
+pub(crate) struct SendSlot<'a> {
+    _prompt: MutexGuard<'a, ()>,
+    reserved: Option<(&'a NonceCache, (Address, U256), U256)>,
+}
+
+impl SendSlot<'_> {
+    /// Returns the hash of the submitted transaction, or the error of a failed send. The error
+    /// comes with whether the transaction never reached Frame.
+    ///
+    /// The reserved nonce is released if the transaction never reached Frame or Frame refused
+    /// it. A send that failed without an answer from Frame, e.g. because it timed out, may still
+    /// be waiting for approval or already be broadcast, so its nonce stays reserved.
+    pub(crate) async fn settle(self, sent: Result<H256, (FrameError, bool)>) -> Result<H256> {
+        let (err, unsent) = match sent {
+            Ok(tx_hash) => return Ok(tx_hash),
+            Err(failure) => failure,
+        };
+        if let Some((nonces, key, nonce)) = self.reserved {
+            if unsent || err.is_refusal() {
+                nonces.release(key, nonce).await;
+            }
+        }
+        Err(err)
+    }
+}
+
+/// A transaction that was sent through Frame but may not be mined yet.
+///
+/// The hash is available right away with [`PendingTx::hash`]. Awaiting the handle polls for
+/// the receipt, after the number of confirmations set with [`PendingTx::confirmations`]
+/// (one by default). A receipt with status 0 is returned as `FrameError::TransactionReverted`,
+/// with the reason recovered by replaying the transaction if possible; receipts of
+/// pre-Byzantium chains have no status and are always returned.
+///
+/// If the node no longer knows a transaction it has seen, e.g. because it was evicted from the
+/// mempool, it is considered dropped once
+/// [`FrameClientBuilder::dropped_after_polls`](crate::builder::FrameClientBuilder::dropped_after_polls)
+/// polls in a row found nothing, and `FrameError::TransactionDropped` is returned. Polls before
+/// the transaction first appears don't count. With
+/// [`FrameClientBuilder::auto_resubmit`](crate::builder::FrameClientBuilder::auto_resubmit),
+/// the same transaction, with the same nonce, is sent through Frame again instead, and the
+/// receipt of the resubmission is returned. If another transaction with the same nonce was
+/// mined instead, e.g. a replacement sent with [`FrameClient::speed_up`], the transaction is
+/// neither counted as dropped nor resubmitted, and `FrameError::TransactionReplaced` is
+/// returned.
+#[must_use = "the receipt is only polled for when the pending transaction is awaited"]
+pub struct PendingTx<'a, P> {
+    tx_hash: H256,
+    client: &'a FrameClient<P>,
+    confirmations: usize,
+    interval: Option<Duration>,
+    resubmit: Option<TypedTransaction>,
+}
+
+impl<'a, P: JsonRpcClient> PendingTx<'a, P> {
+    /// Tracks a transaction that was submitted without going through the provider.
+    pub(crate) fn new(tx_hash: H256, client: &'a FrameClient<P>) -> Self {
+        Self {
+            tx_hash,
+            client,
+            confirmations: 1,
+            interval: None,
+            resubmit: None,
+        }
+    }
+
+    /// Returns the hash of the transaction.
+    pub fn hash(&self) -> H256 {
+        self.tx_hash
+    }
+
+    /// Sets the number of blocks, counting the one the transaction is mined in, to wait for
+    /// before the receipt is returned.
+    pub fn confirmations(self, confirmations: usize) -> Self {
+        Self {
+            confirmations,
+            ..self
+        }
+    }
+
+    /// Sets how often the receipt is polled. Defaults to the provider's polling interval.
+    pub fn interval(self, interval: Duration) -> Self {
+        Self {
+            interval: Some(interval),
+            ..self
+        }
+    }
+
+    /// Polls for the receipt of `tx_hash`, returning the transaction as it was last seen if it
+    /// left the mempool instead.
+    ///
+    /// A transaction counts as gone once it was seen and then missing for
+    /// `dropped_after_polls` polls in a row, so one that is slow to reach the node's pool is
+    /// waited for. Failed polls are retried, like those of an ethers `PendingTransaction`.
+    async fn poll_receipt(&self, tx_hash: H256) -> Result<TransactionReceipt, Transaction> {
+        let provider = &self.client.provider;
+        let interval = self.interval.unwrap_or_else(|| provider.get_interval());
+        let (mut seen, mut missing) = (None, 0);
+        loop {
+            match provider.get_transaction(tx_hash).await {
+                Ok(Some(tx)) => {
+                    missing = 0;
+                    if tx.block_number.is_some() {
+                        let receipt = confirmed_receipt(provider, tx_hash, self.confirmations);
+                        if let Ok(Some(receipt)) = receipt.await {
+                            return Ok(receipt);
+                        }
+                    }
+                    seen = Some(tx);
+                }
+                Ok(None) if seen.is_some() => {
+                    missing += 1;
+                    if missing >= self.client.dropped_after_polls.max(1) {
+                        return Err(seen.unwrap_or_default());
+                    }
+                }
+                _ => {}
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+impl<'a, P: JsonRpcClient + 'a> IntoFuture for PendingTx<'a, P> {
+    type Output = Result<TransactionReceipt>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let mut tx_hash = self.tx_hash;
+            let mut resubmissions = 0;
+            let receipt = loop {
+                let gone = match self.poll_receipt(tx_hash).await {
+                    Ok(receipt) => break receipt,
+                    Err(gone) => gone,
+                };
+                // A replacement, e.g. from `speed_up` or `cancel`, takes the transaction out of
+                // the mempool without a receipt of its own; resubmitting it would be futile.
+                if self.client.get_confirmed_nonce(gone.from).await? > gone.nonce {
+                    return Err(FrameError::TransactionReplaced {
+                        hash: tx_hash,
+                        nonce: gone.nonce,
+                    });
+                }
+                let drops = &self.client.drop_stats;
+                drops.dropped.fetch_add(1, Ordering::Relaxed);
+                match &self.resubmit {
+                    Some(tx) if resubmissions < MAX_RESUBMISSIONS => {
+                        tx_hash = self.client.resubmit(tx.clone()).await?;
+                        drops.resubmitted.fetch_add(1, Ordering::Relaxed);
+                        resubmissions += 1;
+                    }
+                    _ => return Err(FrameError::TransactionDropped { hash: tx_hash }),
+                }
+            };
+            if receipt.status == Some(0.into()) {
+                let reason = replay_revert_reason(&self.client.provider, tx_hash, None).await;
+                return Err(FrameError::TransactionReverted {
+                    hash: tx_hash,
+                    gas_used: receipt.gas_used,
+                    reason: reason.ok(),
+                });
+            }
+            Ok(receipt)
+        })
+    }
+}
+
+/// How many pending transactions of a client were dropped and resubmitted, returned by
+/// [`FrameClient::drop_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DropStats {
+    /// The number of times a pending transaction was found dropped.
+    pub dropped: u64,
+    /// The number of dropped transactions that were resubmitted.
+    pub resubmitted: u64,
+}
+
+/// The counters behind [`DropStats`], shared by the clones of a client.
+#[derive(Debug, Default)]
+pub(crate) struct DropCounters {
+    dropped: AtomicU64,
+    resubmitted: AtomicU64,
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Sends the native gas token from one address to another without waiting for the
+    /// transaction to be mined.
+    ///
+    /// This behaves like [`FrameClient::send_gas_token`], but returns as soon as Frame has
+    /// submitted the transaction. The returned [`PendingTx`] carries the hash and can be awaited
+    /// for the receipt later, so several transactions can be in flight at once.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` from which the gas token will be sent.
+    /// - `to`: The `Address` to which the gas token will be sent.
+    /// - `amount`: The amount of the gas token to send, specified in Wei as a `U256`.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `PendingTx` of the submitted transaction.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let pending = client.send_gas_token_pending(from, to, U256::exp10(18)).await?;
+    ///     println!("Submitted {:?}", pending.hash());
+    ///
+    ///     let receipt = pending.confirmations(3).await?;
+    ///     println!("Mined in block {:?}", receipt.block_number);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the transaction fails to be sent. If the user declines the transaction
+    /// in Frame, the error is `FrameError::UserRejected`.
+    pub async fn send_gas_token_pending(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<PendingTx<'_, P>> {
+        let tx = TransactionRequest::new().from(from).to(to).value(amount);
+        self.send_pending(tx).await
+    }
+
+    /// Returns how many pending transactions of this client and its clones were dropped from
+    /// the mempool and resubmitted, e.g. to log how often it happens.
+    pub fn drop_stats(&self) -> DropStats {
+        DropStats {
+            dropped: self.drop_stats.dropped.load(Ordering::Relaxed),
+            resubmitted: self.drop_stats.resubmitted.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sends a transaction through Frame, returning once it has been submitted.
+    ///
+    /// The transaction goes through [`FrameClient::prepare_send`] first. With automatic access
+    /// lists enabled, a contract call without an access list then gets one. With automatic
+    /// resubmission enabled, the nonce and fees are filled in before the transaction is sent, so
+    /// that it can be resubmitted unchanged if it is dropped.
+    pub(crate) async fn send_pending<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+    ) -> Result<PendingTx<'_, P>> {
+        let mut tx = tx.into();
+        let slot = self.prepare_send(&mut tx).await?;
+        if self.auto_access_list {
+            self.attach_access_list(&mut tx).await;
+        }
+
+        let filled = async {
+            if self.auto_resubmit {
+                if let (Some(from), None) = (tx.from().copied(), tx.nonce()) {
+                    tx.set_nonce(self.get_pending_nonce(from).await?);
+                }
+            }
+            self.provider.fill_transaction(&mut tx, None).await?;
+            Ok(())
+        };
+        let sent = match filled.await {
+            Ok(()) => match self.provider.request("eth_sendTransaction", [&tx]).await {
+                Ok(tx_hash) => Ok(tx_hash),
+                Err(err) => Err((FrameError::from(err), false)),
+            },
+            Err(err) => Err((err, true)),
+        };
+        let tx_hash = slot.settle(sent).await?;
+        Ok(PendingTx {
+            resubmit: self.auto_resubmit.then_some(tx),
+            ..PendingTx::new(tx_hash, self)
+        })
+    }
+
+    /// Runs the steps every send takes before its transaction is submitted to Frame.
+    ///
+    /// A before-send hook sees the transaction first, and the transaction policy is enforced on
+    /// its result. With simulation before sending enabled, a transaction that would revert is
+    /// not sent. After any other open prompt in Frame is answered, Frame must still be connected
+    /// to the expected chain, and with nonce management enabled, a transaction without a nonce
+    /// gets the next one of its sender. The returned slot holds the prompt until it is dropped.
+    pub(crate) async fn prepare_send(&self, tx: &mut TypedTransaction) -> Result<SendSlot<'_>> {
+        self.approve_send(tx).await?;
+        self.enforce_policy(tx).await?;
+        if self.simulate_before_send {
+            self.ensure_simulation_succeeds(tx).await?;
+        }
+        let prompt = self.lock_prompt().await?;
+        self.ensure_expected_chain().await?;
+        let reserved = match (&self.nonces, tx.from(), tx.nonce()) {
+            (Some(nonces), Some(from), None) => {
+                let (key, nonce) = self.reserve_nonce(nonces, *from).await?;
+                tx.set_nonce(nonce);
+                Some((nonces, key, nonce))
+            }
+            _ => None,
+        };
+        Ok(SendSlot {
+            _prompt: prompt,
+            reserved,
+        })
+    }
+
+    /// Sends a dropped transaction through Frame again, as it was sent the first time.
+    async fn resubmit(&self, tx: TypedTransaction) -> Result<H256> {
+        let _prompt = self.lock_prompt().await?;
+        self.ensure_expected_chain().await?;
+        let pending = self.provider.send_transaction(tx, None).await?;
+        Ok(pending.tx_hash())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{push_mined, push_receipt};
+    use ethers::{
+        providers::{JsonRpcError, MockResponse},
+        types::Bytes,
+    };
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn test_send_gas_token_pending() {
+        let (client, mock) = FrameClient::mocked();
+        let tx_hash = H256::random();
+        push_mined(&mock, tx_hash);
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+
+        let pending = client
+            .send_gas_token_pending(Address::random(), Address::random(), U256::from(1))
+            .await
+            .unwrap();
+        assert_eq!(pending.hash(), tx_hash);
+
+        let receipt = pending.await.unwrap();
+        assert_eq!(receipt.transaction_hash, tx_hash);
+    }
+
+    #[tokio::test]
+    async fn test_reverted_transaction_is_an_error() {
+        let (client, mock) = FrameClient::mocked();
+        let tx_hash = H256::random();
+        let mined = Transaction {
+            hash: tx_hash,
+            block_number: Some(1.into()),
+            ..Default::default()
+        };
+        // The replay that recovers the reason hits an assertion.
+        let mut panic = ethers::utils::id("Panic(uint256)").to_vec();
+        panic.extend(ethers::abi::encode(&[ethers::abi::Token::Uint(1.into())]));
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_string(),
+            data: Some(json!(Bytes::from(panic))),
+        }));
+        mock.push(mined).unwrap();
+        push_receipt(
+            &mock,
+            TransactionReceipt {
+                transaction_hash: tx_hash,
+                block_number: Some(1.into()),
+                status: Some(0.into()),
+                gas_used: Some(30_000.into()),
+                ..Default::default()
+            },
+        );
+        mock.push(tx_hash).unwrap();
+        mock.push(U256::from(30_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+
+        let err = client
+            .send_gas_token(Address::random(), Address::random(), U256::from(1))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("transaction {:?} reverted: panic code 0x1", tx_hash)
+        );
+        assert!(matches!(
+            err,
+            FrameError::TransactionReverted { hash, gas_used: Some(gas), .. }
+                if hash == tx_hash && gas == U256::from(30_000)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_send_checks_expected_chain() {
+        let (mut client, mock) = FrameClient::mocked();
+        *client.expected_chain_id.write().await = Some(U256::from(42_161));
+        mock.push(U256::from(1)).unwrap();
+
+        let err = client
+            .send_gas_token_pending(Address::random(), Address::random(), U256::from(1))
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            FrameError::ChainMismatch { expected, actual }
+                if expected == U256::from(42_161) && actual == U256::one()
+        ));
+        mock.assert_request("eth_chainId", ()).unwrap();
+        assert!(mock.assert_request("eth_gasPrice", ()).is_err());
+
+        client.allow_chain_drift = true;
+        let tx_hash = H256::random();
+        mock.push(tx_hash).unwrap();
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        let pending = client
+            .send_gas_token_pending(Address::random(), Address::random(), U256::from(1))
+            .await
+            .unwrap();
+        assert_eq!(pending.hash(), tx_hash);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_transaction() {
+        let (mut client, mock) = FrameClient::mocked();
+        client.dropped_after_polls = 2;
+        let (from, to) = (Address::random(), Address::random());
+        let tx_hash = H256::random();
+        let pooled = Transaction {
+            hash: tx_hash,
+            from,
+            nonce: U256::from(3),
+            ..Default::default()
+        };
+        // The nonce is still unused, so the transaction wasn't replaced.
+        mock.push(U256::from(3)).unwrap();
+        // Missing polls only count once the transaction was seen, and only in a row.
+        let polls = [false, false, true, false, true, false, false];
+        for found in polls.into_iter().rev() {
+            if found {
+                mock.push(pooled.clone()).unwrap();
+            } else {
+                mock.push(Value::Null).unwrap();
+            }
+        }
+        mock.push(tx_hash).unwrap();
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+
+        let err = client
+            .send_gas_token(from, to, U256::from(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FrameError::TransactionDropped { hash } if hash == tx_hash));
+        assert_eq!(
+            client.drop_stats(),
+            DropStats {
+                dropped: 1,
+                resubmitted: 0
+            }
+        );
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+        let estimated: TypedTransaction = TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .value(1)
+            .gas_price(1)
+            .into();
+        mock.assert_request("eth_estimateGas", [&estimated])
+            .unwrap();
+        let mut sent = estimated;
+        sent.set_gas(21_000);
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+        for _ in polls {
+            mock.assert_request("eth_getTransactionByHash", [tx_hash])
+                .unwrap();
+        }
+        mock.assert_request("eth_getTransactionCount", (from, "latest"))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replaced_transaction_is_not_resubmitted() {
+        let (mut client, mock) = FrameClient::mocked();
+        client.auto_resubmit = true;
+        client.dropped_after_polls = 1;
+        let from = Address::random();
+        let tx_hash = H256::random();
+        // A replacement with nonce 5 was mined, so the account's next nonce is 6.
+        mock.push(U256::from(6)).unwrap();
+        mock.push(Value::Null).unwrap();
+        mock.push(Transaction {
+            hash: tx_hash,
+            from,
+            nonce: U256::from(5),
+            ..Default::default()
+        })
+        .unwrap();
+        mock.push(tx_hash).unwrap();
+
+        let tx = TransactionRequest::new()
+            .from(from)
+            .to(Address::random())
+            .nonce(5)
+            .gas(21_000)
+            .gas_price(1);
+        let err = client
+            .send_pending(tx.clone())
+            .await
+            .unwrap()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FrameError::TransactionReplaced { hash, nonce }
+                if hash == tx_hash && nonce == U256::from(5)
+        ));
+        assert_eq!(client.drop_stats(), DropStats::default());
+
+        let sent: TypedTransaction = tx.into();
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+        mock.assert_request("eth_getTransactionByHash", [tx_hash])
+            .unwrap();
+        mock.assert_request("eth_getTransactionByHash", [tx_hash])
+            .unwrap();
+        mock.assert_request("eth_getTransactionCount", (from, "latest"))
+            .unwrap();
+        // The stale original was not sent through Frame again.
+        assert!(mock.assert_request("eth_sendTransaction", ()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_auto_resubmit_dropped_transaction() {
+        let (mut client, mock) = FrameClient::mocked();
+        client.auto_resubmit = true;
+        client.dropped_after_polls = 1;
+        let (from, to) = (Address::random(), Address::random());
+        let (dropped, resubmitted) = (H256::random(), H256::random());
+        push_mined(&mock, resubmitted);
+        mock.push(U256::from(5)).unwrap();
+        mock.push(Value::Null).unwrap();
+        mock.push(Transaction {
+            hash: dropped,
+            from,
+            nonce: U256::from(5),
+            ..Default::default()
+        })
+        .unwrap();
+        mock.push(dropped).unwrap();
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        mock.push(U256::from(5)).unwrap();
+
+        let receipt = client
+            .send_gas_token_pending(from, to, U256::from(1))
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(receipt.transaction_hash, resubmitted);
+        assert_eq!(
+            client.drop_stats(),
+            DropStats {
+                dropped: 1,
+                resubmitted: 1
+            }
+        );
+
+        let estimated: TypedTransaction = TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .value(1)
+            .nonce(5)
+            .gas_price(1)
+            .into();
+        let mut sent = estimated.clone();
+        sent.set_gas(21_000);
+        mock.assert_request("eth_getTransactionCount", (from, "pending"))
+            .unwrap();
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+        mock.assert_request("eth_estimateGas", [estimated]).unwrap();
+        mock.assert_request("eth_sendTransaction", [&sent]).unwrap();
+        mock.assert_request("eth_getTransactionByHash", [dropped])
+            .unwrap();
+        mock.assert_request("eth_getTransactionByHash", [dropped])
+            .unwrap();
+        mock.assert_request("eth_getTransactionCount", (from, "latest"))
+            .unwrap();
+        // The resubmission reuses the nonce and fees of the dropped transaction.
+        mock.assert_request("eth_sendTransaction", [&sent]).unwrap();
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{
+    client::FrameClient,
+    error::{FrameError, Result},
+};
+use ethers::{
+    providers::JsonRpcClient,
+    types::{transaction::eip2718::TypedTransaction, Address, NameOrAddress, U256},
+};
+use std::collections::HashSet;
+
+/// Limits enforced on every transaction before it is sent to Frame or signed by it.
+///
+/// The policy is set with
+/// [`FrameClientBuilder::policy`](crate::builder::FrameClientBuilder::policy) and can be read
+/// and replaced at runtime with [`FrameClient::policy`] and [`FrameClient::set_policy`]; clones
+/// of a client share it. A transaction that breaks a rule fails with
+/// `FrameError::PolicyViolation` naming the rule. The default policy allows everything.
+///
+/// The limits apply to the transaction as it is handed to Frame. Fees the user raises in the
+/// Frame UI before approving are not checked.
+///
+/// # Examples
+/// ```no_run
+/// use ethers::types::{Address, U256};
+/// use frame_rs::{client::FrameClient, policy::TxPolicy};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let treasury: Address = "0x...".parse()?;
+///     let client = FrameClient::builder()
+///         .policy(TxPolicy {
+///             max_value: Some(U256::exp10(18)),
+///             allowed_recipients: Some([treasury].into()),
+///             ..Default::default()
+///         })
+///         .build()
+///         .await?;
+///
+///     // Tighten the limit later without reconnecting.
+///     let mut policy = client.policy().await;
+///     policy.max_value = Some(U256::exp10(17));
+///     client.set_policy(policy).await;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TxPolicy {
+    /// The maximum value of a transaction, in Wei.
+    pub max_value: Option<U256>,
+    /// The maximum gas price, or max fee per gas of an EIP-1559 transaction, in Wei. A
+    /// transaction without fees has them filled in from the provider to be checked.
+    pub max_gas_price: Option<U256>,
+    /// The only recipients transactions may be sent to. Contract deployments, which have no
+    /// recipient, are rejected when this is set.
+    pub allowed_recipients: Option<HashSet<Address>>,
+    /// Recipients transactions may never be sent to.
+    pub denied_recipients: HashSet<Address>,
+}
+
+impl TxPolicy {
+    /// Checks `tx` against every rule of the policy.
+    ///
+    /// # Errors
+    /// Returns `FrameError::PolicyViolation` for the first rule `tx` breaks. A recipient given
+    /// as an ENS name breaks the recipient rules, since it can't be checked before it is
+    /// resolved.
+    pub fn check(&self, tx: &TypedTransaction) -> Result<()> {
+        let to = match tx.to() {
+            Some(NameOrAddress::Address(to)) => Some(*to),
+            Some(NameOrAddress::Name(name)) => {
+                if let Some(rule) = self.recipient_rule() {
+                    return Err(violation(
+                        rule,
+                        format!("recipient {} is an ENS name and can't be checked", name),
+                    ));
+                }
+                None
+            }
+            None => None,
+        };
+        self.check_fields(to, tx.value().copied(), tx.gas_price())
+    }
+
+    /// Checks the recipient, value and gas price of a transaction. `to` is `None` for a
+    /// contract deployment.
+    pub(crate) fn check_fields(
+        &self,
+        to: Option<Address>,
+        value: Option<U256>,
+        gas_price: Option<U256>,
+    ) -> Result<()> {
+        if let (Some(max), Some(value)) = (self.max_value, value) {
+            if value > max {
+                return Err(violation(
+                    "max_value",
+                    format!("value {} wei exceeds the maximum of {} wei", value, max),
+                ));
+            }
+        }
+        if let (Some(max), Some(gas_price)) = (self.max_gas_price, gas_price) {
+            if gas_price > max {
+                return Err(violation(
+                    "max_gas_price",
+                    format!(
+                        "gas price {} wei exceeds the maximum of {} wei",
+                        gas_price, max
+                    ),
+                ));
+            }
+        }
+        if let Some(to) = to {
+            if self.denied_recipients.contains(&to) {
+                return Err(violation(
+                    "denied_recipients",
+                    format!("recipient {:?} is denied", to),
+                ));
+            }
+        }
+        if let Some(allowed) = &self.allowed_recipients {
+            match to {
+                Some(to) if allowed.contains(&to) => {}
+                Some(to) => {
+                    return Err(violation(
+                        "allowed_recipients",
+                        format!("recipient {:?} is not allowed", to),
+                    ))
+                }
+                None => {
+                    return Err(violation(
+                        "allowed_recipients",
+                        "contract deployments have no allowed recipient".to_string(),
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn recipient_rule(&self) -> Option<&'static str> {
+        if self.allowed_recipients.is_some() {
+            Some("allowed_recipients")
+        } else if !self.denied_recipients.is_empty() {
+            Some("denied_recipients")
+        } else {
+            None
+        }
+    }
+}
+
+fn violation(rule: &'static str, message: String) -> FrameError {
+    FrameError::PolicyViolation { rule, message }
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Returns a copy of the client's current [`TxPolicy`].
+    pub async fn policy(&self) -> TxPolicy {
+        self.policy.read().await.clone()
+    }
+
+    /// Replaces the client's [`TxPolicy`], for this client and all of its clones. Transactions
+    /// already being sent keep the policy they were checked against.
+    pub async fn set_policy(&self, policy: TxPolicy) {
+        *self.policy.write().await = policy;
+    }
+
+    /// Checks `tx` against the client's policy, filling in its fees first if the policy limits
+    /// them and `tx` has none. EIP-1559 fees are left out on chains without a base fee.
+    pub(crate) async fn enforce_policy(&self, tx: &mut TypedTransaction) -> Result<()> {
+        let policy = self.policy().await;
+        if policy.max_gas_price.is_some() && tx.gas_price().is_none() {
+            match &mut *tx {
+                TypedTransaction::Eip1559(tx) => {
+                    if let Some(fees) = self.estimate_fee_overrides().await? {
+                        tx.max_fee_per_gas = Some(fees.max_fee_per_gas);
+                        tx.max_priority_fee_per_gas
+                            .get_or_insert(fees.max_priority_fee_per_gas);
+                    }
+                }
+                tx => {
+                    tx.set_gas_price(self.get_gas_price().await?);
+                }
+            }
+        }
+        policy.check(tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::TransactionRequest;
+
+    #[test]
+    fn test_check_policy() {
+        let (allowed, denied) = (Address::random(), Address::random());
+        let policy = TxPolicy {
+            max_value: Some(U256::from(100)),
+            max_gas_price: Some(U256::from(10)),
+            allowed_recipients: Some([allowed, denied].into()),
+            denied_recipients: [denied].into(),
+        };
+        let tx = TransactionRequest::new()
+            .to(allowed)
+            .value(100)
+            .gas_price(10);
+        assert!(policy.check(&tx.clone().into()).is_ok());
+
+        let rule = |tx: TransactionRequest| match policy.check(&tx.into()) {
+            Err(FrameError::PolicyViolation { rule, .. }) => rule,
+            other => panic!("expected a policy violation, got {:?}", other),
+        };
+        assert_eq!(rule(tx.clone().value(101)), "max_value");
+        assert_eq!(rule(tx.clone().gas_price(11)), "max_gas_price");
+        assert_eq!(rule(tx.clone().to(denied)), "denied_recipients");
+        assert_eq!(rule(tx.clone().to(Address::random())), "allowed_recipients");
+        assert_eq!(rule(tx.clone().to("vitalik.eth")), "allowed_recipients");
+        assert_eq!(
+            rule(TransactionRequest::new().value(1)),
+            "allowed_recipients"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_violating_policy() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        client
+            .set_policy(TxPolicy {
+                max_gas_price: Some(U256::from(10)),
+                ..Default::default()
+            })
+            .await;
+        mock.push(U256::from(11)).unwrap();
+
+        let err = client
+            .send_gas_token_pending(from, to, U256::from(1))
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            FrameError::PolicyViolation {
+                rule: "max_gas_price",
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "transaction violates policy rule max_gas_price: gas price 11 wei exceeds the \
+             maximum of 10 wei"
+        );
+        // The transaction never reached Frame.
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+        assert!(mock.assert_request("eth_sendTransaction", ()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_violating_policy() {
+        let (client, _) = FrameClient::mocked();
+        let denied = Address::random();
+        client
+            .set_policy(TxPolicy {
+                denied_recipients: [denied].into(),
+                ..Default::default()
+            })
+            .await;
+        let shared = client.clone();
+
+        let tx = TransactionRequest::new().from(Address::random()).to(denied);
+        let err = shared.sign_transaction(tx).await.unwrap_err();
+        assert!(matches!(
+            err,
+            FrameError::PolicyViolation {
+                rule: "denied_recipients",
+                ..
+            }
+        ));
+        assert_eq!(client.policy().await.denied_recipients.len(), 1);
+    }
+}
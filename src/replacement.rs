@@ -0,0 +1,410 @@
+use crate::{
+    client::FrameClient,
+    error::{bail, ensure, FrameError, Result},
+};
+use ethers::{
+    providers::JsonRpcClient,
+    types::{
+        transaction::{eip2718::TypedTransaction, eip2930::AccessList},
+        Bytes, Transaction, H256, U256,
+    },
+};
+
+/// The fee bump applied by [`FrameClient::speed_up`] by default, in tenths of a percent.
+pub const DEFAULT_FEE_BUMP_PERMILLE: u32 = 125;
+
+/// The smallest fee bump most nodes accept for a replacement transaction, in percent.
+pub const MIN_FEE_BUMP_PERCENT: u32 = 10;
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Replaces a pending transaction with a copy that pays higher fees, to get a stuck
+    /// transaction mined.
+    ///
+    /// The replacement keeps the nonce, recipient, value, data and gas limit of the original.
+    /// Its gas price, or its EIP-1559 fee caps, are raised by `fee_bump_percent`, or by 12.5%
+    /// if `None`, and it is sent through Frame for the user to approve. Only one of the two
+    /// transactions can be mined, so callers should watch both hashes until one of them is.
+    ///
+    /// # Parameters
+    /// - `tx_hash`: The `H256` hash of the pending transaction.
+    /// - `fee_bump_percent`: By how much to raise the fees, in percent. Nodes reject
+    ///   replacements that raise them by less than 10%.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` hash of the replacement.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::client::FrameClient;
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let pending = client.send_gas_token_pending(from, to, U256::exp10(18)).await?;
+    ///     let original = pending.hash();
+    ///     tokio::time::sleep(Duration::from_secs(120)).await;
+    ///     if client.get_transaction_receipt(original).await?.is_none() {
+    ///         let replacement = client.speed_up(original, Some(20)).await?;
+    ///         println!("Replaced {:?} with {:?}", original, replacement);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the transaction is unknown or already mined, if `fee_bump_percent`
+    /// is below 10%, or if the replacement fails to be sent. Blob transactions and other types
+    /// besides legacy, EIP-2930 and EIP-1559 can't be replaced, which is reported as
+    /// `FrameError::Unsupported`. If the user declines the replacement in Frame, the error is
+    /// `FrameError::UserRejected`.
+    pub async fn speed_up(&self, tx_hash: H256, fee_bump_percent: Option<u32>) -> Result<H256> {
+        self.replace(tx_hash, fee_bump_percent, |_| {}).await
+    }
+
+    /// Cancels a pending transaction by replacing it with a transfer of zero to its own sender,
+    /// with the same nonce and higher fees.
+    ///
+    /// Fees are raised like in [`FrameClient::speed_up`]. Any access list of the original is
+    /// dropped, so the gas limit of 21000 covers the cancellation. The original transaction may
+    /// still be mined before the cancellation, in which case the node rejects the cancellation with
+    /// "nonce too low". This is detected and reported as `FrameError::AlreadyMined`, naming the
+    /// original transaction and its block. Otherwise, callers should watch both hashes until
+    /// one of them is mined.
+    ///
+    /// # Parameters
+    /// - `tx_hash`: The `H256` hash of the pending transaction.
+    /// - `fee_bump_percent`: By how much to raise the fees, in percent. Defaults to 12.5%.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` hash of the cancellation.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{H256, U256};
+    /// use frame_rs::{client::FrameClient, error::FrameError};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let stuck: H256 = "0x...".parse()?;
+    ///
+    ///     match client.cancel(stuck, None).await {
+    ///         Ok(cancellation) => println!("Cancelling with {:?}", cancellation),
+    ///         Err(FrameError::AlreadyMined { block, .. }) => {
+    ///             println!("Too late: mined in block {}", block)
+    ///         }
+    ///         Err(err) => return Err(err.into()),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `FrameError::AlreadyMined` if the transaction was mined before or while it was
+    /// cancelled. Returns another error if the transaction is unknown, if `fee_bump_percent` is
+    /// below 10%, or if the cancellation fails to be sent.
+    pub async fn cancel(&self, tx_hash: H256, fee_bump_percent: Option<u32>) -> Result<H256> {
+        self.replace(tx_hash, fee_bump_percent, |tx| {
+            let from = *tx
+                .from()
+                .expect("transactions fetched from a node have a sender");
+            tx.set_to(from);
+            tx.set_value(U256::zero());
+            tx.set_data(Bytes::new());
+            // A plain transfer touches no storage, and an access list would cost more gas
+            // than the 21000 it is given.
+            tx.set_access_list(AccessList::default());
+            tx.set_gas(21_000);
+        })
+        .await
+    }
+
+    /// Sends a copy of a pending transaction, modified by `edit`, with bumped fees.
+    async fn replace(
+        &self,
+        tx_hash: H256,
+        fee_bump_percent: Option<u32>,
+        edit: impl FnOnce(&mut TypedTransaction),
+    ) -> Result<H256> {
+        let permille = match fee_bump_percent {
+            Some(percent) => {
+                ensure!(
+                    percent >= MIN_FEE_BUMP_PERCENT,
+                    "A fee bump of {}% is below the {}% nodes require for a replacement",
+                    percent,
+                    MIN_FEE_BUMP_PERCENT
+                );
+                percent * 10
+            }
+            None => DEFAULT_FEE_BUMP_PERMILLE,
+        };
+
+        let original = self.get_pending_transaction(tx_hash).await?;
+        // ethers converts types it doesn't know, including blob transactions, to legacy ones,
+        // which would drop fields the original relies on.
+        match original.transaction_type.map(|kind| kind.as_u64()) {
+            None | Some(0..=2) => {}
+            Some(kind) => {
+                return Err(FrameError::Unsupported(format!(
+                    "replacing type {} transactions",
+                    kind
+                )))
+            }
+        }
+        let mut replacement: TypedTransaction = (&original).into();
+        edit(&mut replacement);
+        bump_fees(&mut replacement, permille);
+
+        match self.send_pending(replacement).await {
+            Ok(pending) => Ok(pending.hash()),
+            // The original was mined while the user was approving the replacement.
+            Err(err) if err.to_string().to_lowercase().contains("nonce too low") => {
+                self.get_pending_transaction(tx_hash).await?;
+                Err(err)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Fetches a transaction that has not been mined yet.
+    pub(crate) async fn get_pending_transaction(&self, tx_hash: H256) -> Result<Transaction> {
+        let Some(tx) = self.get_transaction(tx_hash).await? else {
+            bail!("Tx {:?} not found; it may have been dropped", tx_hash);
+        };
+        if let Some(block) = tx.block_number {
+            return Err(FrameError::AlreadyMined { tx_hash, block });
+        }
+        Ok(tx)
+    }
+}
+
+/// Raises the gas price or EIP-1559 fee caps of `tx` by `permille` tenths of a percent,
+/// rounding up.
+pub(crate) fn bump_fees(tx: &mut TypedTransaction, permille: u32) {
+    let bump = |fee: Option<U256>| fee.map(|fee| (fee * (1000 + permille) + 999) / 1000);
+    match tx {
+        TypedTransaction::Eip1559(tx) => {
+            tx.max_fee_per_gas = bump(tx.max_fee_per_gas);
+            tx.max_priority_fee_per_gas = bump(tx.max_priority_fee_per_gas);
+        }
+        tx => {
+            if let Some(gas_price) = bump(tx.gas_price()) {
+                tx.set_gas_price(gas_price);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        providers::{JsonRpcError, MockResponse},
+        types::{transaction::eip2930::AccessListItem, Address, TransactionRequest},
+    };
+
+    #[test]
+    fn test_bump_fees_rounds_up() {
+        let mut tx: TypedTransaction = TransactionRequest::new().gas_price(100).into();
+        bump_fees(&mut tx, DEFAULT_FEE_BUMP_PERMILLE);
+        assert_eq!(tx.gas_price(), Some(U256::from(113)));
+
+        let mut tx: TypedTransaction = ethers::types::Eip1559TransactionRequest::new()
+            .max_fee_per_gas(40)
+            .max_priority_fee_per_gas(2)
+            .into();
+        bump_fees(&mut tx, 200);
+        let TypedTransaction::Eip1559(tx) = tx else {
+            unreachable!()
+        };
+        assert_eq!(tx.max_fee_per_gas, Some(U256::from(48)));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(U256::from(3)));
+    }
+
+    #[tokio::test]
+    async fn test_speed_up() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let original = Transaction {
+            hash: H256::random(),
+            from,
+            to: Some(to),
+            nonce: U256::from(5),
+            value: U256::from(1),
+            gas: U256::from(21_000),
+            gas_price: Some(U256::from(100)),
+            ..Default::default()
+        };
+        let replacement = H256::random();
+        mock.push(replacement).unwrap();
+        mock.push(original.clone()).unwrap();
+
+        assert_eq!(
+            client.speed_up(original.hash, None).await.unwrap(),
+            replacement
+        );
+        let expected: TypedTransaction = TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .nonce(5)
+            .value(1)
+            .gas(21_000)
+            .gas_price(113)
+            .data(Bytes::new())
+            .into();
+        mock.assert_request("eth_getTransactionByHash", [original.hash])
+            .unwrap();
+        mock.assert_request("eth_sendTransaction", [expected])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_speed_up_refuses_mined_transaction() {
+        let (client, mock) = FrameClient::mocked();
+        let mined = Transaction {
+            block_number: Some(9.into()),
+            ..Default::default()
+        };
+        mock.push(mined.clone()).unwrap();
+
+        let err = client.speed_up(mined.hash, None).await.unwrap_err();
+        assert!(err.to_string().contains("already mined in block 9"));
+        assert!(matches!(err, FrameError::AlreadyMined { tx_hash, .. } if tx_hash == mined.hash));
+        assert!(client.speed_up(mined.hash, Some(5)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_speed_up_refuses_blob_transaction() {
+        let (client, mock) = FrameClient::mocked();
+        let blob = Transaction {
+            hash: H256::random(),
+            transaction_type: Some(3.into()),
+            max_fee_per_gas: Some(U256::from(40)),
+            max_priority_fee_per_gas: Some(U256::from(2)),
+            ..Default::default()
+        };
+        mock.push(blob.clone()).unwrap();
+
+        let err = client.speed_up(blob.hash, None).await.unwrap_err();
+        assert!(
+            matches!(&err, FrameError::Unsupported(feature) if feature == "replacing type 3 transactions"),
+            "{:?}",
+            err
+        );
+        mock.assert_request("eth_getTransactionByHash", [blob.hash])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cancel() {
+        let (client, mock) = FrameClient::mocked();
+        let from = Address::random();
+        let original = Transaction {
+            hash: H256::random(),
+            from,
+            to: Some(Address::random()),
+            nonce: U256::from(5),
+            value: U256::exp10(18),
+            gas: U256::from(60_000),
+            input: Bytes::from(vec![0xde, 0xad]),
+            transaction_type: Some(2.into()),
+            max_fee_per_gas: Some(U256::from(40)),
+            max_priority_fee_per_gas: Some(U256::from(2)),
+            ..Default::default()
+        };
+        let cancellation = H256::random();
+        mock.push(cancellation).unwrap();
+        mock.push(original.clone()).unwrap();
+
+        assert_eq!(
+            client.cancel(original.hash, Some(20)).await.unwrap(),
+            cancellation
+        );
+        let expected: TypedTransaction = ethers::types::Eip1559TransactionRequest::new()
+            .from(from)
+            .to(from)
+            .nonce(5)
+            .value(0)
+            .gas(21_000)
+            .data(Bytes::new())
+            .max_fee_per_gas(48)
+            .max_priority_fee_per_gas(3)
+            .into();
+        mock.assert_request("eth_getTransactionByHash", [original.hash])
+            .unwrap();
+        mock.assert_request("eth_sendTransaction", [expected])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cancel_drops_access_list() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, token) = (Address::random(), Address::random());
+        let original = Transaction {
+            hash: H256::random(),
+            from,
+            to: Some(token),
+            nonce: U256::from(8),
+            gas: U256::from(50_000),
+            input: Bytes::from(vec![0xa9, 0x05, 0x9c, 0xbb]),
+            transaction_type: Some(2.into()),
+            access_list: Some(AccessList(vec![AccessListItem {
+                address: token,
+                storage_keys: vec![H256::random()],
+            }])),
+            max_fee_per_gas: Some(U256::from(40)),
+            max_priority_fee_per_gas: Some(U256::from(2)),
+            ..Default::default()
+        };
+        mock.push(H256::random()).unwrap();
+        mock.push(original.clone()).unwrap();
+
+        client.cancel(original.hash, None).await.unwrap();
+        let expected: TypedTransaction = ethers::types::Eip1559TransactionRequest::new()
+            .from(from)
+            .to(from)
+            .nonce(8)
+            .value(0)
+            .gas(21_000)
+            .data(Bytes::new())
+            .max_fee_per_gas(45)
+            .max_priority_fee_per_gas(3)
+            .into();
+        mock.assert_request("eth_getTransactionByHash", [original.hash])
+            .unwrap();
+        mock.assert_request("eth_sendTransaction", [expected])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cancel_loses_race_to_original() {
+        let (client, mock) = FrameClient::mocked();
+        let pending = Transaction {
+            from: Address::random(),
+            gas_price: Some(U256::from(100)),
+            ..Default::default()
+        };
+        let mined = Transaction {
+            block_number: Some(12.into()),
+            ..pending.clone()
+        };
+        mock.push(mined).unwrap();
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: -32000,
+            message: "nonce too low".to_string(),
+            data: None,
+        }));
+        mock.push(pending.clone()).unwrap();
+
+        let err = client.cancel(pending.hash, None).await.unwrap_err();
+        assert!(matches!(
+            err,
+            FrameError::AlreadyMined { tx_hash, block } if tx_hash == pending.hash && block == 12.into()
+        ));
+    }
+}
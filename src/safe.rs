@@ -0,0 +1,243 @@
+use crate::{
+    abi::function,
+    client::FrameClient,
+    error::{bail, Result},
+};
+use ethers::{
+    abi::Token,
+    middleware::Middleware,
+    providers::JsonRpcClient,
+    types::{
+        transaction::{eip2718::TypedTransaction, eip712::TypedData},
+        Address, Bytes, Signature, TransactionRequest, U256,
+    },
+};
+use serde_json::json;
+
+const NONCE: &str = "function nonce() view returns (uint256)";
+
+/// The kind of call a Safe makes when executing a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SafeOperation {
+    #[default]
+    Call = 0,
+    DelegateCall = 1,
+}
+
+/// A Safe (formerly Gnosis Safe) transaction, as hashed by `getTransactionHash`.
+///
+/// `nonce` is the Safe's nonce. When it is `None`, [`FrameClient::sign_safe_tx`] fetches the
+/// current nonce from the Safe.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SafeTx {
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub operation: SafeOperation,
+    pub safe_tx_gas: U256,
+    pub base_gas: U256,
+    pub gas_price: U256,
+    pub gas_token: Address,
+    pub refund_receiver: Address,
+    pub nonce: Option<U256>,
+}
+
+impl SafeTx {
+    /// Creates a `Call` to `to` with no gas refund, using the Safe's current nonce.
+    pub fn new(to: Address, value: U256, data: Bytes) -> Self {
+        Self {
+            to,
+            value,
+            data,
+            ..Default::default()
+        }
+    }
+
+    /// Builds the EIP-712 typed data of the transaction for `safe` on chain `chain_id`.
+    ///
+    /// The domain has the `chainId` and `verifyingContract` fields used by Safe v1.3.0 and
+    /// later.
+    ///
+    /// # Errors
+    /// Returns an error if `nonce` is not set.
+    pub fn typed_data(&self, safe: Address, chain_id: U256) -> Result<TypedData> {
+        let Some(nonce) = self.nonce else {
+            bail!("Safe transaction has no nonce");
+        };
+
+        let typed_data = json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "SafeTx": [
+                    { "name": "to", "type": "address" },
+                    { "name": "value", "type": "uint256" },
+                    { "name": "data", "type": "bytes" },
+                    { "name": "operation", "type": "uint8" },
+                    { "name": "safeTxGas", "type": "uint256" },
+                    { "name": "baseGas", "type": "uint256" },
+                    { "name": "gasPrice", "type": "uint256" },
+                    { "name": "gasToken", "type": "address" },
+                    { "name": "refundReceiver", "type": "address" },
+                    { "name": "nonce", "type": "uint256" }
+                ]
+            },
+            "primaryType": "SafeTx",
+            "domain": { "chainId": chain_id, "verifyingContract": safe },
+            "message": {
+                "to": self.to,
+                "value": self.value,
+                "data": self.data,
+                "operation": self.operation as u8,
+                "safeTxGas": self.safe_tx_gas,
+                "baseGas": self.base_gas,
+                "gasPrice": self.gas_price,
+                "gasToken": self.gas_token,
+                "refundReceiver": self.refund_receiver,
+                "nonce": nonce
+            }
+        });
+        Ok(serde_json::from_value(typed_data)?)
+    }
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Signs a Safe transaction as one of the Safe's owners.
+    ///
+    /// The transaction is signed with `eth_signTypedData_v4`, so the returned signature has a
+    /// `v` of 27 or 28 and can be submitted to `execTransaction` or the Safe Transaction
+    /// Service as an owner signature as-is. If `tx.nonce` is not set, the Safe's current nonce
+    /// is fetched, and the domain uses the connected chain ID.
+    ///
+    /// # Parameters
+    /// - `safe`: The `Address` of the Safe.
+    /// - `owner`: The `Address` of the Frame account, an owner of the Safe, that signs.
+    /// - `tx`: The `SafeTx` to sign.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the owner's `Signature`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, Bytes, U256};
+    /// use frame_rs::{client::FrameClient, safe::SafeTx};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let safe: Address = "0x...".parse()?;
+    ///     let owner = client.get_accounts().await?[0];
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let tx = SafeTx::new(to, U256::exp10(18), Bytes::new());
+    ///     let signature = client.sign_safe_tx(safe, owner, tx).await?;
+    ///     println!("Owner signature: {}", signature);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the Safe's nonce or the chain ID cannot be fetched, or if signing
+    /// fails. If the user declines the request in Frame, the error is
+    /// `FrameError::UserRejected`.
+    pub async fn sign_safe_tx(
+        &self,
+        safe: Address,
+        owner: Address,
+        mut tx: SafeTx,
+    ) -> Result<Signature> {
+        if tx.nonce.is_none() {
+            tx.nonce = Some(self.safe_nonce(safe).await?);
+        }
+        let typed_data = tx.typed_data(safe, self.get_chain_id().await?)?;
+        self.sign_typed_data(owner, &typed_data).await
+    }
+
+    /// Fetches the current nonce of `safe`.
+    async fn safe_nonce(&self, safe: Address) -> Result<U256> {
+        let nonce = function(NONCE);
+        let data = nonce.encode_input(&[])?;
+        let tx: TypedTransaction = TransactionRequest::new().to(safe).data(data).into();
+
+        let output = self.provider.call(&tx, None).await?;
+        match nonce.decode_output(&output)?.as_slice() {
+            [Token::Uint(nonce)] => Ok(*nonce),
+            tokens => bail!("Unexpected nonce output: {:?}", tokens),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eip712::{domain_hash, struct_hash};
+    use ethers::{types::H256, utils::keccak256};
+
+    fn word(bytes: &[u8]) -> Token {
+        Token::FixedBytes(bytes.to_vec())
+    }
+
+    #[test]
+    fn test_safe_tx_hashes() {
+        let safe: Address = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b"
+            .parse()
+            .unwrap();
+        let tx = SafeTx {
+            nonce: Some(U256::from(5)),
+            ..SafeTx::new(
+                "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+                    .parse()
+                    .unwrap(),
+                U256::exp10(18),
+                Bytes::from(vec![0xde, 0xad, 0xbe, 0xef]),
+            )
+        };
+        let typed_data = tx.typed_data(safe, U256::from(1)).unwrap();
+
+        // DOMAIN_SEPARATOR_TYPEHASH and SAFE_TX_TYPEHASH from the Safe v1.3.0 contract.
+        let domain_type_hash: H256 =
+            "0x47e79534a245952e8b16893a336b85a3d9ea9fa8c573f3d803afb92a79469218"
+                .parse()
+                .unwrap();
+        let safe_tx_type_hash: H256 =
+            "0xbb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8"
+                .parse()
+                .unwrap();
+
+        let expected_domain = keccak256(ethers::abi::encode(&[
+            word(domain_type_hash.as_bytes()),
+            Token::Uint(U256::from(1)),
+            Token::Address(safe),
+        ]));
+        let expected_struct = keccak256(ethers::abi::encode(&[
+            word(safe_tx_type_hash.as_bytes()),
+            Token::Address(tx.to),
+            Token::Uint(tx.value),
+            word(&keccak256(&tx.data)),
+            Token::Uint(U256::zero()),
+            Token::Uint(U256::zero()),
+            Token::Uint(U256::zero()),
+            Token::Uint(U256::zero()),
+            Token::Address(Address::zero()),
+            Token::Address(Address::zero()),
+            Token::Uint(U256::from(5)),
+        ]));
+
+        assert_eq!(
+            domain_hash(&typed_data).unwrap(),
+            H256::from(expected_domain)
+        );
+        assert_eq!(
+            struct_hash(&typed_data).unwrap(),
+            H256::from(expected_struct)
+        );
+    }
+
+    #[test]
+    fn test_safe_tx_requires_nonce() {
+        let tx = SafeTx::new(Address::zero(), U256::zero(), Bytes::new());
+        assert!(tx.typed_data(Address::zero(), U256::from(1)).is_err());
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{
+    abi::function,
+    client::FrameClient,
+    error::{ensure, Result},
+};
+use ethers::{
+    abi::Token,
+    middleware::Middleware,
+    providers::{JsonRpcClient, RpcError},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, Signature, TransactionRequest,
+        H256, U256,
+    },
+    utils::{hash_message, hex},
+};
+
+/// The value returned by `isValidSignature(bytes32,bytes)` for a valid EIP-1271 signature.
+pub const EIP1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+const IS_VALID_SIGNATURE: &str =
+    "function isValidSignature(bytes32 hash, bytes signature) view returns (bytes4)";
+
+/// Recovers the address that signed `message` with `personal_sign` (EIP-191).
+///
+/// The `"\x19Ethereum Signed Message:\n"` prefix is applied to `message` the same way Frame
+/// applies it when signing. Signatures with `v` in either the 27/28 or the 0/1 form are accepted.
+///
+/// # Errors
+/// Returns an error if the signature is malformed and no address can be recovered from it.
+pub fn recover_signer(message: &[u8], signature: &Signature) -> Result<Address> {
+    let signature = signature.normalize_v();
+    Ok(signature.recover(hash_message(message))?)
+}
+
+/// Checks that `signature` is a `personal_sign` (EIP-191) signature of `message` by `expected`.
+///
+/// # Returns
+/// Returns `Ok(true)` if the recovered signer is `expected` and `Ok(false)` otherwise.
+///
+/// # Errors
+/// Returns an error if the signature is malformed and no address can be recovered from it.
+pub fn verify_signature(message: &[u8], signature: &Signature, expected: Address) -> Result<bool> {
+    Ok(recover_signer(message, signature)? == expected)
+}
+
+/// Recovers the address that signed the 32-byte `hash` directly, without applying the EIP-191
+/// prefix. Signatures with `v` in either the 27/28 or the 0/1 form are accepted.
+///
+/// # Errors
+/// Returns an error if the signature is malformed and no address can be recovered from it.
+pub fn recover_hash_signer(hash: H256, signature: &Signature) -> Result<Address> {
+    let signature = signature.normalize_v();
+    Ok(signature.recover(hash)?)
+}
+
+/// Conversions between the signature formats expected by different consumers.
+///
+/// Signatures returned by the signing methods of [`FrameClient`] are already normalized to a `v`
+/// of 27/28. `Signature` itself serializes with serde as its `r`, `s` and `v` components and
+/// parses from 65-byte hex with [`str::parse`].
+pub trait SignatureExt: Sized {
+    /// Returns a copy with a `v` of 0/1 converted into the 27/28 form expected by `ecrecover`
+    /// and most contracts. Other values, such as EIP-155 `v`s, are left untouched.
+    fn normalize_v(&self) -> Self;
+
+    /// Formats the signature as `0x`-prefixed 65-byte hex (`r ‖ s ‖ v`).
+    fn to_hex(&self) -> String;
+
+    /// Splits the signature into its `r`, `s` and normalized `v` components.
+    fn to_rsv(&self) -> (H256, H256, u8);
+
+    /// Encodes the signature in the 64-byte EIP-2098 compact form (`r ‖ yParity · 2²⁵⁵ + s`).
+    fn to_compact(&self) -> [u8; 64];
+
+    /// Builds a signature from its `r`, `s` and `v` components.
+    fn from_rsv(r: H256, s: H256, v: u8) -> Self;
+
+    /// Decodes a signature from the 64-byte EIP-2098 compact form.
+    ///
+    /// # Errors
+    /// Returns an error if `compact` is not 64 bytes long.
+    fn from_compact(compact: &[u8]) -> Result<Self>;
+}
+
+impl SignatureExt for Signature {
+    fn normalize_v(&self) -> Self {
+        let mut signature = *self;
+        if signature.v < 27 {
+            signature.v += 27;
+        }
+        signature
+    }
+
+    fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.to_vec()))
+    }
+
+    fn to_rsv(&self) -> (H256, H256, u8) {
+        let signature = self.normalize_v();
+        (
+            u256_to_h256(signature.r),
+            u256_to_h256(signature.s),
+            signature.v as u8,
+        )
+    }
+
+    fn to_compact(&self) -> [u8; 64] {
+        let (r, s, v) = self.to_rsv();
+        let mut compact = [0u8; 64];
+        compact[..32].copy_from_slice(r.as_bytes());
+        compact[32..].copy_from_slice(s.as_bytes());
+        if v == 28 {
+            compact[32] |= 0x80;
+        }
+        compact
+    }
+
+    fn from_rsv(r: H256, s: H256, v: u8) -> Self {
+        Signature {
+            r: U256::from_big_endian(r.as_bytes()),
+            s: U256::from_big_endian(s.as_bytes()),
+            v: u64::from(v),
+        }
+        .normalize_v()
+    }
+
+    fn from_compact(compact: &[u8]) -> Result<Self> {
+        ensure!(
+            compact.len() == 64,
+            "EIP-2098 signature must be 64 bytes, got {}",
+            compact.len()
+        );
+        let mut y_parity_and_s = [0u8; 32];
+        y_parity_and_s.copy_from_slice(&compact[32..]);
+        let v = 27 + (y_parity_and_s[0] >> 7);
+        y_parity_and_s[0] &= 0x7f;
+        Ok(Self::from_rsv(
+            H256::from_slice(&compact[..32]),
+            H256(y_parity_and_s),
+            v,
+        ))
+    }
+}
+
+/// Converts a signature component into its big-endian 32-byte form.
+fn u256_to_h256(value: U256) -> H256 {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    H256(bytes)
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Verifies a signature of `hash` by `signer`, supporting both EOAs and smart contract
+    /// accounts such as Safe (EIP-1271).
+    ///
+    /// If `signer` has code (checked with `eth_getCode`), `isValidSignature(bytes32,bytes)` is
+    /// called on it and the signature is valid when it returns the `0x1626ba7e` magic value.
+    /// Otherwise the signer is recovered from `hash` with ecrecover and compared to `signer`.
+    ///
+    /// # Parameters
+    /// - `signer`: The `Address` of the account that is expected to have signed `hash`.
+    /// - `hash`: The 32-byte hash that was signed, e.g. an EIP-191 or EIP-712 digest.
+    /// - `signature`: The signature bytes. For contracts these are passed through as-is.
+    ///
+    /// # Returns
+    /// Returns `Ok(true)` if the signature is valid for `signer`, and `Ok(false)` if it is not,
+    /// including when the contract reverts or the EOA signature is malformed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::{types::{Address, Bytes, U256}, utils::hash_message};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let safe: Address = "0x...".parse()?;
+    ///     let signature: Bytes = "0x...".parse()?;
+    ///
+    ///     let valid = client
+    ///         .verify_signature_onchain(safe, hash_message("hello frame"), signature)
+    ///         .await?;
+    ///     println!("Signature valid: {}", valid);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the code lookup or the contract call fails for a reason other than a
+    /// JSON-RPC error response, for example when the node is not reachable.
+    pub async fn verify_signature_onchain(
+        &self,
+        signer: Address,
+        hash: H256,
+        signature: Bytes,
+    ) -> Result<bool> {
+        let code = self.provider.get_code(signer, None).await?;
+        if code.is_empty() {
+            let recovered = Signature::try_from(signature.as_ref())
+                .ok()
+                .and_then(|signature| recover_hash_signer(hash, &signature).ok());
+            return Ok(recovered == Some(signer));
+        }
+
+        let data = function(IS_VALID_SIGNATURE).encode_input(&[
+            Token::FixedBytes(hash.as_bytes().to_vec()),
+            Token::Bytes(signature.to_vec()),
+        ])?;
+        let tx: TypedTransaction = TransactionRequest::new().to(signer).data(data).into();
+
+        match self.provider.call(&tx, None).await {
+            Ok(result) => Ok(result.get(..4) == Some(&EIP1271_MAGIC_VALUE[..])),
+            Err(err) if err.is_error_response() => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::providers::{JsonRpcError, MockResponse};
+
+    const MESSAGE: &[u8] = b"Some data";
+    const SIGNER: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+    const SIGNATURE: &str = "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+
+    #[test]
+    fn test_recover_signer() {
+        let signature: Signature = SIGNATURE.parse().unwrap();
+        let signer: Address = SIGNER.parse().unwrap();
+
+        assert_eq!(signature.v, 28);
+        assert_eq!(recover_signer(MESSAGE, &signature).unwrap(), signer);
+        assert!(verify_signature(MESSAGE, &signature, signer).unwrap());
+        assert!(!verify_signature(b"Other data", &signature, signer).unwrap());
+    }
+
+    #[test]
+    fn test_recover_signer_zero_one_v() {
+        let mut signature: Signature = SIGNATURE.parse().unwrap();
+        signature.v = 1;
+
+        let signer: Address = SIGNER.parse().unwrap();
+        assert!(verify_signature(MESSAGE, &signature, signer).unwrap());
+    }
+
+    #[test]
+    fn test_signature_format_round_trips() {
+        let signature: Signature = SIGNATURE.parse().unwrap();
+
+        let hex = signature.to_hex();
+        assert_eq!(hex, format!("0x{}", SIGNATURE));
+        assert_eq!(hex.parse::<Signature>().unwrap(), signature);
+
+        let (r, s, v) = signature.to_rsv();
+        assert_eq!(v, 28);
+        assert_eq!(Signature::from_rsv(r, s, v), signature);
+
+        let compact = signature.to_compact();
+        assert_eq!(compact[32] & 0x80, 0x80);
+        assert_eq!(Signature::from_compact(&compact).unwrap(), signature);
+
+        let mut zero_one = signature;
+        zero_one.v = 1;
+        assert_eq!(zero_one.normalize_v(), signature);
+        assert_eq!(zero_one.to_compact(), compact);
+        assert!(Signature::from_compact(&compact[..63]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_signature_onchain_eoa() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push::<Bytes, _>(Bytes::new()).unwrap();
+
+        let signature: Bytes = SIGNATURE.parse().unwrap();
+        let valid = client
+            .verify_signature_onchain(SIGNER.parse().unwrap(), hash_message(MESSAGE), signature)
+            .await
+            .unwrap();
+        assert!(valid);
+    }
+
+    #[tokio::test]
+    async fn test_verify_signature_onchain_contract() {
+        let (client, mock) = FrameClient::mocked();
+        let mut magic = EIP1271_MAGIC_VALUE.to_vec();
+        magic.resize(32, 0);
+        mock.push::<Bytes, _>(Bytes::from(magic)).unwrap();
+        mock.push::<Bytes, _>(Bytes::from(vec![0x60, 0x80]))
+            .unwrap();
+
+        let valid = client
+            .verify_signature_onchain(Address::random(), H256::random(), Bytes::new())
+            .await
+            .unwrap();
+        assert!(valid);
+    }
+
+    #[tokio::test]
+    async fn test_verify_signature_onchain_contract_revert() {
+        let (client, mock) = FrameClient::mocked();
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_string(),
+            data: None,
+        }));
+        mock.push::<Bytes, _>(Bytes::from(vec![0x60, 0x80]))
+            .unwrap();
+
+        let valid = client
+            .verify_signature_onchain(Address::random(), H256::random(), Bytes::new())
+            .await
+            .unwrap();
+        assert!(!valid);
+    }
+}
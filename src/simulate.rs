@@ -0,0 +1,245 @@
+use crate::{
+    client::FrameClient,
+    error::{FrameError, Result},
+};
+use ethers::{
+    middleware::Middleware,
+    providers::{JsonRpcClient, ProviderError},
+    types::{transaction::eip2718::TypedTransaction, BlockNumber, Bytes, TransactionRequest, U256},
+};
+
+/// The outcome of a transaction simulated with [`FrameClient::simulate`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SimulationResult {
+    /// Whether the transaction would succeed.
+    pub success: bool,
+    /// The estimated gas of the transaction, if it would succeed.
+    pub gas_used: Option<U256>,
+    /// The return data of the call, or the raw revert data if it would revert.
+    pub return_data: Bytes,
+    /// The decoded revert reason, if the transaction would revert.
+    pub revert_reason: Option<String>,
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Simulates a transaction against the pending block with `eth_call` and `eth_estimateGas`,
+    /// without opening a prompt in Frame.
+    ///
+    /// The simulation runs from the `from` address of `tx`, or from the account selected in
+    /// Frame if it has none, since that is the account that would sign it.
+    ///
+    /// # Parameters
+    /// - `tx`: The `TransactionRequest` to simulate.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `SimulationResult`. A transaction that
+    /// would revert is a successful simulation with `success` set to `false`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, Bytes, TransactionRequest, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from = client.get_accounts().await?[0];
+    ///     let to: Address = "0x...".parse()?;
+    ///     let data: Bytes = "0x...".parse()?;
+    ///
+    ///     let tx = TransactionRequest::new().from(from).to(to).data(data);
+    ///     let simulation = client.simulate(&tx).await?;
+    ///     match simulation.revert_reason {
+    ///         Some(reason) => println!("Would revert: {}", reason),
+    ///         None => println!("Would use {:?} gas", simulation.gas_used),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the simulation itself fails, for example because the provider can't
+    /// be reached.
+    pub async fn simulate(&self, tx: &TransactionRequest) -> Result<SimulationResult> {
+        let tx: TypedTransaction = tx.clone().into();
+        self.simulate_typed(tx).await
+    }
+
+    /// Simulates `tx` like [`FrameClient::simulate`] and returns `FrameError::ExecutionReverted`
+    /// if it would revert, so sends can abort before the user is prompted.
+    pub(crate) async fn ensure_simulation_succeeds(&self, tx: &TypedTransaction) -> Result<()> {
+        let simulation = self.simulate_typed(tx.clone()).await?;
+        match simulation.revert_reason {
+            Some(reason) => Err(FrameError::ExecutionReverted {
+                reason,
+                data: Some(simulation.return_data).filter(|data| !data.is_empty()),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    async fn simulate_typed(&self, mut tx: TypedTransaction) -> Result<SimulationResult> {
+        if tx.from().is_none() {
+            if let Some(account) = self.get_accounts().await?.first() {
+                tx.set_from(*account);
+            }
+        }
+
+        let block = Some(BlockNumber::Pending.into());
+        let simulated: Result<_, ProviderError> = async {
+            let return_data = self.provider.call(&tx, block).await?;
+            let gas = self.provider.estimate_gas(&tx, block).await?;
+            Ok((return_data, gas))
+        }
+        .await;
+        match simulated.map_err(FrameError::from_revert) {
+            Ok((return_data, gas)) => Ok(SimulationResult {
+                success: true,
+                gas_used: Some(gas),
+                return_data,
+                revert_reason: None,
+            }),
+            Err(FrameError::ExecutionReverted { reason, data }) => Ok(SimulationResult {
+                success: false,
+                gas_used: None,
+                return_data: data.unwrap_or_default(),
+                revert_reason: Some(reason),
+            }),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blob::{BlobTxRequest, VERSIONED_HASH_VERSION_KZG};
+    use ethers::{
+        abi::{encode, Token},
+        providers::{JsonRpcError, MockResponse},
+        types::{Address, Eip1559TransactionRequest, H256},
+        utils::id,
+    };
+    use serde_json::json;
+
+    fn revert(reason: &str) -> (Bytes, MockResponse) {
+        let mut data = id("Error(string)").to_vec();
+        data.extend(encode(&[Token::String(reason.to_string())]));
+        let data = Bytes::from(data);
+        let response = MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: format!("execution reverted: {}", reason),
+            data: Some(json!(data)),
+        });
+        (data, response)
+    }
+
+    #[tokio::test]
+    async fn test_simulate() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let (data, response) = revert("Ownable: caller is not the owner");
+        mock.push_response(response);
+        mock.push(U256::from(46_000)).unwrap();
+        mock.push::<Bytes, _>(Bytes::from_static(&[0x01])).unwrap();
+
+        let tx = TransactionRequest::new().from(from).to(to);
+        let simulation = client.simulate(&tx).await.unwrap();
+        assert_eq!(
+            simulation,
+            SimulationResult {
+                success: true,
+                gas_used: Some(U256::from(46_000)),
+                return_data: Bytes::from_static(&[0x01]),
+                revert_reason: None,
+            }
+        );
+        let typed: TypedTransaction = tx.clone().into();
+        mock.assert_request("eth_call", (&typed, "pending"))
+            .unwrap();
+        mock.assert_request("eth_estimateGas", (&typed, "pending"))
+            .unwrap();
+
+        let simulation = client.simulate(&tx).await.unwrap();
+        assert!(!simulation.success);
+        assert_eq!(simulation.return_data, data);
+        assert_eq!(
+            simulation.revert_reason.as_deref(),
+            Some("Ownable: caller is not the owner")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_simulate_uses_selected_account() {
+        let (client, mock) = FrameClient::mocked();
+        let account = Address::random();
+        mock.push(U256::from(21_000)).unwrap();
+        mock.push::<Bytes, _>(Bytes::new()).unwrap();
+        mock.push::<Vec<Address>, _>(vec![account]).unwrap();
+
+        let tx = TransactionRequest::new().to(Address::random());
+        client.simulate(&tx).await.unwrap();
+        mock.assert_request("eth_accounts", ()).unwrap();
+        let typed: TypedTransaction = tx.from(account).into();
+        mock.assert_request("eth_call", (&typed, "pending"))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_simulate_before_send() {
+        let (mut client, mock) = FrameClient::mocked();
+        client.simulate_before_send = true;
+        let (data, response) = revert("Insufficient balance");
+        mock.push_response(response);
+
+        let (from, to) = (Address::random(), Address::random());
+
+        let err = client
+            .send_transaction_raw(from, to, Bytes::new(), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FrameError::ExecutionReverted { reason, data: Some(revert) }
+                if reason == "Insufficient balance" && revert == data
+        ));
+        let typed: TypedTransaction = TransactionRequest::new()
+            .from(from)
+            .to(to)
+            .data(Bytes::new())
+            .into();
+        mock.assert_request("eth_call", (&typed, "pending"))
+            .unwrap();
+        // Nothing else was requested, so the user was never prompted.
+        assert!(mock.assert_request("eth_gasPrice", ()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_simulate_before_blob_send() {
+        let (mut client, mock) = FrameClient::mocked();
+        client.simulate_before_send = true;
+        let (_, response) = revert("Unknown batch");
+        mock.push_response(response);
+
+        let mut versioned_hash = H256::random();
+        versioned_hash.0[0] = VERSIONED_HASH_VERSION_KZG;
+        let tx = BlobTxRequest {
+            from: Address::random(),
+            to: Address::random(),
+            blob_versioned_hashes: vec![versioned_hash],
+            ..Default::default()
+        };
+        let err = client.send_blob_transaction(tx.clone()).await.unwrap_err();
+        assert!(matches!(
+            err,
+            FrameError::ExecutionReverted { reason, .. } if reason == "Unknown batch"
+        ));
+        let typed: TypedTransaction = Eip1559TransactionRequest::new()
+            .from(tx.from)
+            .to(tx.to)
+            .into();
+        mock.assert_request("eth_call", (&typed, "pending"))
+            .unwrap();
+        assert!(mock.assert_request("eth_blobBaseFee", ()).is_err());
+    }
+}
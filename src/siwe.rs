@@ -0,0 +1,463 @@
+use crate::{
+    client::FrameClient,
+    error::{bail, ensure, FrameError, Result},
+};
+use anyhow::{anyhow, Context};
+use ethers::{
+    providers::JsonRpcClient,
+    types::{Address, Signature},
+    utils::to_checksum,
+};
+use std::{
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your Ethereum account:";
+
+/// A Sign-In with Ethereum message (EIP-4361).
+///
+/// Messages are created with [`SiweMessage::builder`], formatted with
+/// [`SiweMessage::to_message`] and parsed back from their canonical string form with
+/// [`str::parse`]. When `chain_id` is left unset, [`FrameClient::sign_siwe`] fills it in with
+/// the chain Frame is connected to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiweMessage {
+    pub domain: String,
+    pub address: Address,
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: Option<u64>,
+    pub nonce: String,
+    pub issued_at: String,
+    pub expiration_time: Option<String>,
+    pub not_before: Option<String>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    /// Returns a [`SiweMessageBuilder`] for creating a message with chainable methods.
+    pub fn builder() -> SiweMessageBuilder {
+        SiweMessageBuilder::default()
+    }
+
+    /// Formats the message in the canonical EIP-4361 string form that is signed.
+    ///
+    /// # Errors
+    /// Returns an error if `chain_id` is not set.
+    pub fn to_message(&self) -> Result<String> {
+        let chain_id = self
+            .chain_id
+            .ok_or_else(|| anyhow!("SIWE message has no chain ID"))?;
+
+        let mut message = format!(
+            "{}{}\n{}\n\n",
+            self.domain,
+            HEADER_SUFFIX,
+            to_checksum(&self.address, None)
+        );
+        if let Some(statement) = &self.statement {
+            message.push_str(statement);
+            message.push('\n');
+        }
+        message.push('\n');
+
+        message.push_str(&format!("URI: {}\n", self.uri));
+        message.push_str(&format!("Version: {}\n", self.version));
+        message.push_str(&format!("Chain ID: {}\n", chain_id));
+        message.push_str(&format!("Nonce: {}\n", self.nonce));
+        message.push_str(&format!("Issued At: {}", self.issued_at));
+        if let Some(expiration_time) = &self.expiration_time {
+            message.push_str(&format!("\nExpiration Time: {}", expiration_time));
+        }
+        if let Some(not_before) = &self.not_before {
+            message.push_str(&format!("\nNot Before: {}", not_before));
+        }
+        if let Some(request_id) = &self.request_id {
+            message.push_str(&format!("\nRequest ID: {}", request_id));
+        }
+        if !self.resources.is_empty() {
+            message.push_str("\nResources:");
+            for resource in &self.resources {
+                message.push_str(&format!("\n- {}", resource));
+            }
+        }
+
+        Ok(message)
+    }
+}
+
+impl FromStr for SiweMessage {
+    type Err = FrameError;
+
+    /// Parses a message from its canonical EIP-4361 string form.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut lines = s.split('\n').peekable();
+
+        let header = lines.next().context("SIWE message is empty")?;
+        let domain = header
+            .strip_suffix(HEADER_SUFFIX)
+            .context("SIWE message has an invalid header")?
+            .to_string();
+        let address = lines
+            .next()
+            .context("SIWE message has no address")?
+            .parse()
+            .context("SIWE message has an invalid address")?;
+        ensure!(
+            lines.next() == Some(""),
+            "SIWE message is missing the blank line after the address"
+        );
+
+        let statement = match lines.next() {
+            Some("") => None,
+            Some(statement) => {
+                ensure!(
+                    lines.next() == Some(""),
+                    "SIWE message is missing the blank line after the statement"
+                );
+                Some(statement.to_string())
+            }
+            None => bail!("SIWE message is truncated"),
+        };
+
+        let mut field = |name: &str| -> Result<String> {
+            Ok(lines
+                .next()
+                .and_then(|line| line.strip_prefix(name))
+                .and_then(|line| line.strip_prefix(": "))
+                .map(str::to_string)
+                .with_context(|| format!("SIWE message is missing the {} field", name))?)
+        };
+
+        let uri = field("URI")?;
+        let version = field("Version")?;
+        let chain_id = field("Chain ID")?
+            .parse()
+            .context("SIWE message has an invalid chain ID")?;
+        let nonce = field("Nonce")?;
+        let issued_at = field("Issued At")?;
+
+        let mut optional = |name: &str| -> Option<String> {
+            let value = lines
+                .peek()?
+                .strip_prefix(name)?
+                .strip_prefix(": ")?
+                .to_string();
+            lines.next();
+            Some(value)
+        };
+
+        let expiration_time = optional("Expiration Time");
+        let not_before = optional("Not Before");
+        let request_id = optional("Request ID");
+
+        let mut resources = Vec::new();
+        if lines.peek() == Some(&"Resources:") {
+            lines.next();
+            while let Some(resource) = lines.peek().and_then(|line| line.strip_prefix("- ")) {
+                resources.push(resource.to_string());
+                lines.next();
+            }
+        }
+        ensure!(
+            lines.next().is_none(),
+            "SIWE message has unexpected trailing content"
+        );
+
+        Ok(SiweMessage {
+            domain,
+            address,
+            statement,
+            uri,
+            version,
+            chain_id: Some(chain_id),
+            nonce,
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        })
+    }
+}
+
+/// A builder for [`SiweMessage`].
+///
+/// `domain`, `address`, `uri` and `nonce` are required. `version` defaults to `1` and
+/// `issued_at` to the current time.
+#[derive(Debug, Clone, Default)]
+pub struct SiweMessageBuilder {
+    domain: Option<String>,
+    address: Option<Address>,
+    statement: Option<String>,
+    uri: Option<String>,
+    chain_id: Option<u64>,
+    nonce: Option<String>,
+    issued_at: Option<String>,
+    expiration_time: Option<String>,
+    not_before: Option<String>,
+    request_id: Option<String>,
+    resources: Vec<String>,
+}
+
+impl SiweMessageBuilder {
+    /// Sets the RFC 3986 authority requesting the sign-in, e.g. `example.com`.
+    pub fn domain(mut self, domain: &str) -> Self {
+        self.domain = Some(domain.to_string());
+        self
+    }
+
+    /// Sets the address of the account signing in.
+    pub fn address(mut self, address: Address) -> Self {
+        self.address = Some(address);
+        self
+    }
+
+    /// Sets the human-readable statement shown to the user.
+    pub fn statement(mut self, statement: &str) -> Self {
+        self.statement = Some(statement.to_string());
+        self
+    }
+
+    /// Sets the URI of the resource that is the subject of the signing.
+    pub fn uri(mut self, uri: &str) -> Self {
+        self.uri = Some(uri.to_string());
+        self
+    }
+
+    /// Sets the chain ID. If unset, `FrameClient::sign_siwe` uses the connected chain.
+    pub fn chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Sets the nonce, which must be at least 8 alphanumeric characters.
+    pub fn nonce(mut self, nonce: &str) -> Self {
+        self.nonce = Some(nonce.to_string());
+        self
+    }
+
+    /// Sets the RFC 3339 issuance time. Defaults to the current time.
+    pub fn issued_at(mut self, issued_at: &str) -> Self {
+        self.issued_at = Some(issued_at.to_string());
+        self
+    }
+
+    /// Sets the RFC 3339 time after which the message is no longer valid.
+    pub fn expiration_time(mut self, expiration_time: &str) -> Self {
+        self.expiration_time = Some(expiration_time.to_string());
+        self
+    }
+
+    /// Sets the RFC 3339 time before which the message is not yet valid.
+    pub fn not_before(mut self, not_before: &str) -> Self {
+        self.not_before = Some(not_before.to_string());
+        self
+    }
+
+    /// Sets a system-specific request identifier.
+    pub fn request_id(mut self, request_id: &str) -> Self {
+        self.request_id = Some(request_id.to_string());
+        self
+    }
+
+    /// Adds a resource URI the user wishes to have resolved as part of the authentication.
+    pub fn resource(mut self, resource: &str) -> Self {
+        self.resources.push(resource.to_string());
+        self
+    }
+
+    /// Builds the message.
+    ///
+    /// # Errors
+    /// Returns an error if a required field is missing, if the nonce is shorter than 8
+    /// characters or not alphanumeric, or if the statement contains a newline.
+    pub fn build(self) -> Result<SiweMessage> {
+        let nonce = self.nonce.context("SIWE message requires a nonce")?;
+        ensure!(
+            nonce.len() >= 8 && nonce.chars().all(|c| c.is_ascii_alphanumeric()),
+            "SIWE nonce must be at least 8 alphanumeric characters"
+        );
+        ensure!(
+            !self.statement.as_deref().unwrap_or_default().contains('\n'),
+            "SIWE statement must not contain a newline"
+        );
+
+        Ok(SiweMessage {
+            domain: self.domain.context("SIWE message requires a domain")?,
+            address: self.address.context("SIWE message requires an address")?,
+            statement: self.statement,
+            uri: self.uri.context("SIWE message requires a URI")?,
+            version: "1".to_string(),
+            chain_id: self.chain_id,
+            nonce,
+            issued_at: self.issued_at.unwrap_or_else(now_rfc3339),
+            expiration_time: self.expiration_time,
+            not_before: self.not_before,
+            request_id: self.request_id,
+            resources: self.resources,
+        })
+    }
+}
+
+/// Formats the current UTC time as an RFC 3339 timestamp, e.g. `2024-01-01T00:00:00Z`.
+fn now_rfc3339() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since the Unix epoch (Howard Hinnant's algorithm).
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60
+    )
+}
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Signs a Sign-In with Ethereum (EIP-4361) message with `personal_sign`.
+    ///
+    /// If the message has no chain ID, the chain Frame is currently connected to is used, so the
+    /// signed message always matches the connected network.
+    ///
+    /// # Parameters
+    /// - `msg`: The `SiweMessage` to sign. It is signed by `msg.address`.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the exact message string that was signed
+    /// together with its `Signature`, ready to be sent to the backend for verification.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::{client::FrameClient, siwe::SiweMessage};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let message = SiweMessage::builder()
+    ///         .domain("example.com")
+    ///         .address(client.get_accounts().await?[0])
+    ///         .uri("https://example.com/login")
+    ///         .statement("Sign in to Example")
+    ///         .nonce("32891756")
+    ///         .build()?;
+    ///
+    ///     let (message, signature) = client.sign_siwe(&message).await?;
+    ///     println!("{}\n{}", message, signature);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the message's chain ID differs from the connected chain, if the
+    /// connected chain cannot be queried, or if signing fails or is rejected in Frame.
+    pub async fn sign_siwe(&self, msg: &SiweMessage) -> Result<(String, Signature)> {
+        let connected = self.get_chain_id().await?.as_u64();
+        if let Some(chain_id) = msg.chain_id {
+            ensure!(
+                chain_id == connected,
+                "SIWE message is for chain {} but Frame is connected to chain {}",
+                chain_id,
+                connected
+            );
+        }
+
+        let message = SiweMessage {
+            chain_id: Some(connected),
+            ..msg.clone()
+        }
+        .to_message()?;
+        let signature = self.sign_message(msg.address, &message).await?;
+        Ok((message, signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = "service.invalid wants you to sign in with your Ethereum account:
+0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2
+
+I accept the ServiceOrg Terms of Service: https://service.invalid/tos
+
+URI: https://service.invalid/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    #[test]
+    fn test_parse_and_format_round_trip() {
+        let message: SiweMessage = MESSAGE.parse().unwrap();
+
+        assert_eq!(message.domain, "service.invalid");
+        assert_eq!(message.chain_id, Some(1));
+        assert_eq!(message.nonce, "32891756");
+        assert_eq!(message.resources.len(), 2);
+        assert_eq!(message.to_message().unwrap(), MESSAGE);
+    }
+
+    #[test]
+    fn test_build_without_statement() {
+        let message = SiweMessage::builder()
+            .domain("example.com")
+            .address(
+                "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"
+                    .parse()
+                    .unwrap(),
+            )
+            .uri("https://example.com")
+            .chain_id(10)
+            .nonce("abcdefgh1")
+            .issued_at("2024-01-01T00:00:00Z")
+            .expiration_time("2024-01-02T00:00:00Z")
+            .build()
+            .unwrap();
+
+        let formatted = message.to_message().unwrap();
+        assert!(formatted.contains("Cc2\n\n\nURI: https://example.com\n"));
+        assert_eq!(formatted.parse::<SiweMessage>().unwrap(), message);
+    }
+
+    #[test]
+    fn test_build_rejects_short_nonce() {
+        let result = SiweMessage::builder()
+            .domain("example.com")
+            .address(Address::zero())
+            .uri("https://example.com")
+            .nonce("short")
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_now_rfc3339_format() {
+        let now = now_rfc3339();
+        assert_eq!(now.len(), 20);
+        assert!(now.ends_with('Z'));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{client::FrameClient, error::Result, logs::LogFilter};
+use ethers::{
+    middleware::Middleware,
+    providers::Ws,
+    types::{Block, Log, H256},
+};
+use futures::{Stream, StreamExt};
+
+impl FrameClient<Ws> {
+    /// Subscribes to new block headers with `eth_subscribe("newHeads")`.
+    ///
+    /// The stream yields every new block as soon as the node announces it, and ends when the
+    /// WebSocket connection to Frame is closed. The subscription is removed with
+    /// `eth_unsubscribe` when the stream is dropped. Subscriptions are only available on clients
+    /// created with [`FrameClient::new_ws`]; HTTP clients don't have these methods, so using one
+    /// is a compile error rather than a runtime failure. The stream borrows the client;
+    /// to consume it on another task, move a clone of the client into the task and subscribe
+    /// there.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps a `Send` stream of blocks with the hashes of
+    /// their transactions.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::U256;
+    /// use frame_rs::client::FrameClient;
+    /// use futures::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new_ws(U256::from(1), None, None).await?;
+    ///
+    ///     let mut blocks = client.subscribe_blocks().await?;
+    ///     while let Some(block) = blocks.next().await {
+    ///         println!("New block: {:?}", block?.number);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the node rejects the subscription.
+    pub async fn subscribe_blocks(
+        &self,
+    ) -> Result<impl Stream<Item = Result<Block<H256>>> + Send + '_> {
+        let stream = self.provider.subscribe_blocks().await?;
+        Ok(stream.map(Ok))
+    }
+
+    /// Subscribes to the hashes of transactions entering the node's transaction pool with
+    /// `eth_subscribe("newPendingTransactions")`.
+    ///
+    /// This behaves like [`FrameClient::subscribe_blocks`]. Not every node exposes its
+    /// transaction pool, in which case the subscription is rejected.
+    ///
+    /// # Errors
+    /// Returns an error if the node rejects the subscription.
+    pub async fn subscribe_pending_transactions(
+        &self,
+    ) -> Result<impl Stream<Item = Result<H256>> + Send + '_> {
+        let stream = self.provider.subscribe_pending_txs().await?;
+        Ok(stream.map(Ok))
+    }
+
+    /// Subscribes to logs matching a filter with `eth_subscribe("logs")`.
+    ///
+    /// Only logs of new blocks are streamed; the block range of `filter` is ignored by most
+    /// nodes. Use [`FrameClient::get_logs`] for past events. Like
+    /// [`FrameClient::subscribe_blocks`], the subscription is removed with `eth_unsubscribe`
+    /// when the stream is dropped.
+    ///
+    /// # Parameters
+    /// - `filter`: The `LogFilter` selecting the contract addresses and topics. An ethers
+    ///   `Filter` can be passed as `&filter.into()`.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps a `Send` stream of matching logs.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::{client::FrameClient, logs::LogFilter};
+    /// use futures::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new_ws(U256::from(1), None, None).await?;
+    ///     let usdc: Address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse()?;
+    ///     let filter = LogFilter::builder()
+    ///         .address(usdc)
+    ///         .event("Transfer(address,address,uint256)")
+    ///         .build()?;
+    ///
+    ///     let mut logs = client.subscribe_logs(&filter).await?;
+    ///     while let Some(log) = logs.next().await {
+    ///         println!("Transfer in {:?}", log?.transaction_hash);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the node rejects the subscription.
+    pub async fn subscribe_logs(
+        &self,
+        filter: &LogFilter,
+    ) -> Result<impl Stream<Item = Result<Log>> + Send + '_> {
+        let stream = self.provider.subscribe_logs(filter.as_filter()).await?;
+        Ok(stream.map(Ok))
+    }
+}
@@ -0,0 +1,241 @@
+use crate::{
+    client::FrameClient,
+    error::{bail, Result},
+    types::GasStrategy,
+};
+use ethers::{
+    providers::JsonRpcClient,
+    types::{Address, TransactionReceipt, TransactionRequest, H256, U256},
+};
+
+impl<P: JsonRpcClient> FrameClient<P> {
+    /// Sends the entire balance of the gas token of `from` to `to`, minus the gas of the
+    /// transfer, to empty the account.
+    ///
+    /// The gas limit of a plain transfer is estimated and sent with the transaction. On
+    /// EIP-1559 networks the fee caps are estimated with
+    /// [`FrameClient::estimate_fee_overrides`] and the amount is `balance - gas_limit *
+    /// max_fee_per_gas`: the transaction can never cost more than that, so it can't fail for
+    /// insufficient funds, but any part of the max fee that isn't charged stays in the account.
+    /// On other networks the current gas price is used and nothing is left behind. Use
+    /// [`FrameClient::send_all_gas_token_with_receipt`] to learn how much was left.
+    ///
+    /// If the user raises the fees in Frame before approving, the transaction no longer fits in
+    /// the balance and is rejected by the node.
+    ///
+    /// # Parameters
+    /// - `from`: The `Address` to empty.
+    /// - `to`: The `Address` to which the balance will be sent.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `H256` transaction hash of the transfer.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let tx_hash = client.send_all_gas_token(from, to).await?;
+    ///     println!("Transaction hash: {:?}", tx_hash);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error stating the required gas if the balance doesn't cover it, and an error if
+    /// the gas or the fees cannot be estimated, if the transaction fails to be sent or if no
+    /// receipt is returned for it.
+    pub async fn send_all_gas_token(&self, from: Address, to: Address) -> Result<H256> {
+        let (receipt, _) = self.send_all_gas_token_with_receipt(from, to).await?;
+        Ok(receipt.transaction_hash)
+    }
+
+    /// Sends the entire balance of the gas token of `from` to `to` like
+    /// [`FrameClient::send_all_gas_token`], and returns the receipt with the amount left in the
+    /// account.
+    ///
+    /// # Returns
+    /// Returns a `Result` that, on success, wraps the `TransactionReceipt` of the transfer and
+    /// the dust left in `from`, in Wei: the part of the reserved gas fee that wasn't charged.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use ethers::types::{Address, U256};
+    /// use frame_rs::client::FrameClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = FrameClient::new(U256::from(1), None, None).await?;
+    ///     let from: Address = "0x...".parse()?;
+    ///     let to: Address = "0x...".parse()?;
+    ///
+    ///     let (receipt, dust) = client.send_all_gas_token_with_receipt(from, to).await?;
+    ///     println!("Emptied in {:?}, {} wei left", receipt.transaction_hash, dust);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`FrameClient::send_all_gas_token`].
+    pub async fn send_all_gas_token_with_receipt(
+        &self,
+        from: Address,
+        to: Address,
+    ) -> Result<(TransactionReceipt, U256)> {
+        let transfer = TransactionRequest::new().from(from).to(to);
+        let gas = self.estimate_gas(&transfer).await?;
+        let (strategy, fee_per_gas) = match self.estimate_fee_overrides().await? {
+            Some(fees) => (fees.into(), fees.max_fee_per_gas),
+            None => {
+                let gas_price = self.get_gas_price().await?;
+                (GasStrategy::Legacy(gas_price), gas_price)
+            }
+        };
+        let balance = self.get_balance(from, None).await?;
+
+        let max_gas_cost = gas.saturating_mul(fee_per_gas);
+        let Some(amount) = balance.checked_sub(max_gas_cost).filter(|a| !a.is_zero()) else {
+            bail!(
+                "Balance of {:?} ({} wei) doesn't cover the gas of the transfer: {} gas at {} wei \
+                 per gas requires {} wei",
+                from,
+                balance,
+                gas,
+                fee_per_gas,
+                max_gas_cost
+            );
+        };
+
+        let tx = strategy.apply(transfer.value(amount).gas(gas));
+        let receipt = self.send_and_wait_for_receipt(tx).await?;
+        let gas_cost = receipt
+            .gas_used
+            .unwrap_or(gas)
+            .saturating_mul(receipt.effective_gas_price.unwrap_or(fee_per_gas));
+        let dust = max_gas_cost.saturating_sub(gas_cost);
+        Ok((receipt, dust))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::push_receipt;
+    use ethers::types::{transaction::eip2718::TypedTransaction, Block};
+
+    #[tokio::test]
+    async fn test_send_all_gas_token_eip1559() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let tx_hash = H256::random();
+        push_receipt(
+            &mock,
+            TransactionReceipt {
+                transaction_hash: tx_hash,
+                block_number: Some(1.into()),
+                gas_used: Some(21_000.into()),
+                effective_gas_price: Some(12.into()),
+                ..Default::default()
+            },
+        );
+        mock.push(tx_hash).unwrap();
+        mock.push(U256::from(1_000_000)).unwrap();
+        mock.push(U256::from(2)).unwrap();
+        mock.push(Block::<H256> {
+            base_fee_per_gas: Some(10.into()),
+            ..Default::default()
+        })
+        .unwrap();
+        mock.push(U256::from(21_000)).unwrap();
+
+        let (receipt, dust) = client
+            .send_all_gas_token_with_receipt(from, to)
+            .await
+            .unwrap();
+        assert_eq!(receipt.transaction_hash, tx_hash);
+        // The max fee of 22 wei per gas was reserved, but only 12 were charged.
+        assert_eq!(dust, U256::from(21_000 * 10));
+
+        let transfer = TransactionRequest::new().from(from).to(to);
+        let estimated: TypedTransaction = transfer.clone().into();
+        mock.assert_request("eth_estimateGas", [estimated]).unwrap();
+        mock.assert_request("eth_getBlockByNumber", ("latest", false))
+            .unwrap();
+        mock.assert_request("eth_maxPriorityFeePerGas", ()).unwrap();
+        mock.assert_request("eth_getBalance", (from, "latest"))
+            .unwrap();
+        let sent = GasStrategy::Eip1559 {
+            max_fee: 22.into(),
+            priority_fee: 2.into(),
+        }
+        .apply(transfer.value(1_000_000 - 21_000 * 22).gas(21_000));
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_all_gas_token_legacy() {
+        let (client, mock) = FrameClient::mocked();
+        let (from, to) = (Address::random(), Address::random());
+        let tx_hash = H256::random();
+        push_receipt(
+            &mock,
+            TransactionReceipt {
+                transaction_hash: tx_hash,
+                block_number: Some(1.into()),
+                gas_used: Some(21_000.into()),
+                ..Default::default()
+            },
+        );
+        mock.push(tx_hash).unwrap();
+        mock.push(U256::from(100_000)).unwrap();
+        mock.push(U256::from(3)).unwrap();
+        mock.push(Block::<H256>::default()).unwrap();
+        mock.push(U256::from(21_000)).unwrap();
+
+        assert_eq!(client.send_all_gas_token(from, to).await.unwrap(), tx_hash);
+
+        let transfer = TransactionRequest::new().from(from).to(to);
+        let estimated: TypedTransaction = transfer.clone().into();
+        mock.assert_request("eth_estimateGas", [estimated]).unwrap();
+        mock.assert_request("eth_getBlockByNumber", ("latest", false))
+            .unwrap();
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+        mock.assert_request("eth_getBalance", (from, "latest"))
+            .unwrap();
+        let sent: TypedTransaction = transfer
+            .value(100_000 - 21_000 * 3)
+            .gas(21_000)
+            .gas_price(3)
+            .into();
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_all_gas_token_insufficient_balance() {
+        let (client, mock) = FrameClient::mocked();
+        let from = Address::random();
+        mock.push(U256::from(63_000)).unwrap();
+        mock.push(U256::from(3)).unwrap();
+        mock.push(Block::<H256>::default()).unwrap();
+        mock.push(U256::from(21_000)).unwrap();
+
+        let err = client
+            .send_all_gas_token(from, Address::random())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Balance of {:?} (63000 wei) doesn't cover the gas of the transfer: 21000 gas at \
+                 3 wei per gas requires 63000 wei",
+                from
+            )
+        );
+    }
+}
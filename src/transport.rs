@@ -0,0 +1,219 @@
+use crate::middleware::MiddlewareStack;
+use async_trait::async_trait;
+use ethers::providers::{HttpClientError, JsonRpcClient, JsonRpcError};
+use reqwest::{Client, StatusCode, Url};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// The HTTP transport used to talk to Frame's JSON-RPC endpoint.
+///
+/// It sends JSON-RPC requests with a shared `reqwest::Client` (which carries the configured
+/// timeouts) and retries requests whose connection failed, such as a refused connection while
+/// Frame is restarting. A request that timed out is not retried, since it may have reached
+/// Frame and be waiting for approval. JSON-RPC error responses are never retried. Responses
+/// with HTTP status 429 or 503 are returned as a `reqwest::Error` carrying the status, so that
+/// a [`RetryMiddleware`](crate::middleware::RetryMiddleware) can retry them. Every request
+/// passes through the configured [`FrameMiddleware`](crate::middleware::FrameMiddleware)s.
+#[derive(Clone, Debug)]
+pub struct FrameTransport {
+    url: Url,
+    next_id: Arc<AtomicU64>,
+    client: Client,
+    max_retries: usize,
+    retry_delay: Duration,
+    middlewares: MiddlewareStack,
+}
+
+impl FrameTransport {
+    /// Creates a transport that sends requests to `url` with `client`, without retries.
+    pub fn new(url: Url, client: Client) -> Self {
+        Self {
+            url,
+            next_id: Arc::new(AtomicU64::new(1)),
+            client,
+            max_retries: 0,
+            retry_delay: Duration::ZERO,
+            middlewares: MiddlewareStack::default(),
+        }
+    }
+
+    /// Retries failed requests up to `max_retries` times, waiting `retry_delay` between attempts.
+    pub fn with_retries(mut self, max_retries: usize, retry_delay: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Passes every request through `middlewares`.
+    pub(crate) fn with_middlewares(mut self, middlewares: MiddlewareStack) -> Self {
+        self.middlewares = middlewares;
+        self
+    }
+
+    /// The `reqwest::Client` shared by all requests sent through this transport.
+    pub(crate) fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for FrameTransport {
+    type Error = HttpClientError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let params = serde_json::to_value(params).map_err(|err| HttpClientError::SerdeJson {
+            err,
+            text: String::new(),
+        })?;
+
+        self.middlewares.before_request(method, &params).await;
+        let started = Instant::now();
+        let result = self.send(method, &params).await;
+        match &result {
+            Ok(value) => {
+                self.middlewares
+                    .after_response(method, value, started.elapsed())
+                    .await
+            }
+            Err(err) => {
+                self.middlewares
+                    .on_error(method, err, started.elapsed())
+                    .await
+            }
+        }
+
+        let value = result?;
+        R::deserialize(&value).map_err(|err| HttpClientError::SerdeJson {
+            err,
+            text: value.to_string(),
+        })
+    }
+}
+
+impl FrameTransport {
+    /// Sends the request, retrying failures to connect and failures that a middleware asks to
+    /// retry.
+    async fn send(&self, method: &str, params: &Value) -> Result<Value, HttpClientError> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let err = match self.send_once(method, params).await {
+                Ok(result) => return Ok(result),
+                Err(err) => err,
+            };
+
+            let delay = match &err {
+                HttpClientError::ReqwestError(err)
+                    if err.is_connect() && attempt <= self.max_retries =>
+                {
+                    Some(self.retry_delay)
+                }
+                err => self.middlewares.retry_after(method, err, attempt).await,
+            };
+            match delay {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Err(err),
+            }
+        }
+    }
+
+    /// Sends the request once and returns the `result` member of the response.
+    async fn send_once(&self, method: &str, params: &Value) -> Result<Value, HttpClientError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let request = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        let response = self
+            .client
+            .post(self.url.clone())
+            .json(&request)
+            .send()
+            .await?;
+        if matches!(
+            response.status(),
+            StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+        ) {
+            response.error_for_status_ref()?;
+        }
+
+        let text = response.text().await?;
+        let mut body: Value =
+            serde_json::from_str(&text).map_err(|err| HttpClientError::SerdeJson {
+                err,
+                text: text.clone(),
+            })?;
+        if let Some(error) = body.get_mut("error").map(Value::take) {
+            let error: JsonRpcError =
+                serde_json::from_value(error).map_err(|err| HttpClientError::SerdeJson {
+                    err,
+                    text: text.clone(),
+                })?;
+            return Err(HttpClientError::JsonRpcError(error));
+        }
+        match body.get_mut("result") {
+            Some(result) => Ok(result.take()),
+            None => Err(HttpClientError::SerdeJson {
+                err: serde::de::Error::missing_field("result"),
+                text,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::U64;
+
+    #[tokio::test]
+    async fn test_retries_connection_errors() {
+        // Nothing listens on port 1 on the loopback interface, so every attempt is refused.
+        let url = Url::parse("http://127.0.0.1:1").unwrap();
+        let transport =
+            FrameTransport::new(url, Client::new()).with_retries(2, Duration::from_millis(50));
+
+        let started = std::time::Instant::now();
+        let result = transport.request::<_, U64>("eth_chainId", ()).await;
+
+        assert!(matches!(result, Err(HttpClientError::ReqwestError(_))));
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_timeouts() {
+        // Accept connections but never answer, like a prompt waiting in Frame.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let accepted = Arc::new(AtomicU64::new(0));
+        let counter = accepted.clone();
+        tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::Relaxed);
+                sockets.push(socket);
+            }
+        });
+        let client = Client::builder()
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let transport = FrameTransport::new(url, client).with_retries(2, Duration::from_millis(10));
+
+        let result = transport
+            .request::<_, String>("eth_sendTransaction", ())
+            .await;
+
+        assert!(matches!(result, Err(HttpClientError::ReqwestError(err)) if err.is_timeout()));
+        assert_eq!(accepted.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,296 @@
+use crate::error::{ensure, Result};
+use ethers::types::{
+    transaction::eip2718::TypedTransaction, Address, Eip1559TransactionRequest, TransactionRequest,
+    U256,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The native currency of a network, as described by EIP-3085.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NativeCurrency {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// The parameters of a `wallet_addEthereumChain` request (EIP-3085).
+///
+/// `chain_id` is serialized as a `0x`-prefixed hex string and optional fields are omitted
+/// from the request when they are `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainConfig {
+    #[serde(serialize_with = "serialize_chain_id")]
+    pub chain_id: U256,
+    pub chain_name: String,
+    pub native_currency: NativeCurrency,
+    pub rpc_urls: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_explorer_urls: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_urls: Option<Vec<String>>,
+}
+
+/// The EIP-3085 name of [`ChainConfig`], the parameters of `wallet_addEthereumChain`.
+pub type AddEthereumChainParams = ChainConfig;
+
+fn serialize_chain_id<S: serde::Serializer>(
+    chain_id: &U256,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("{:#x}", chain_id))
+}
+
+/// The kind of asset of a `wallet_watchAsset` request (EIP-747).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum AssetType {
+    /// An ERC-20 token.
+    #[serde(rename = "ERC20")]
+    Erc20,
+}
+
+/// The asset of a `wallet_watchAsset` request (EIP-747).
+///
+/// `image` is the URL of the token's logo, omitted from the request when it is `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WatchAssetOptions {
+    pub address: Address,
+    pub symbol: String,
+    pub decimals: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+/// A permission to request with `wallet_requestPermissions` (EIP-2255).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionRequest {
+    /// The RPC method the permission grants access to, e.g. `eth_accounts`.
+    pub method: String,
+}
+
+impl PermissionRequest {
+    /// Creates a request for the permission to call `method`.
+    pub fn new(method: &str) -> Self {
+        Self {
+            method: method.to_string(),
+        }
+    }
+}
+
+/// A permission granted to the dapp by the wallet (EIP-2255).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Permission {
+    /// The restrictions on the permission, e.g. the accounts exposed through `eth_accounts`.
+    #[serde(default)]
+    pub caveats: Vec<Caveat>,
+    /// When the permission was granted, in milliseconds since the Unix epoch.
+    pub date: u64,
+    /// The unique identifier of the permission.
+    pub id: String,
+    /// The origin of the dapp the permission was granted to.
+    pub invoker: String,
+    /// The RPC method the permission grants access to, e.g. `eth_accounts`.
+    pub parent_capability: String,
+}
+
+/// A restriction on a [`Permission`], such as the accounts it is limited to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Caveat {
+    /// The kind of restriction, e.g. `restrictReturnedAccounts`.
+    #[serde(rename = "type")]
+    pub caveat_type: String,
+    /// The parameters of the restriction, whose shape depends on its type.
+    pub value: Value,
+}
+
+/// Explicit EIP-1559 fee caps for a transaction, in Wei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeOverrides {
+    /// The maximum total fee per gas, base fee included.
+    pub max_fee_per_gas: U256,
+    /// The maximum priority fee (tip) per gas paid to the block producer.
+    pub max_priority_fee_per_gas: U256,
+}
+
+impl FeeOverrides {
+    /// Builds fee caps that leave room for `base_fee` to double before inclusion.
+    pub(crate) fn from_base_fee(base_fee: U256, priority_fee: U256) -> Self {
+        Self {
+            max_fee_per_gas: base_fee * 2 + priority_fee,
+            max_priority_fee_per_gas: priority_fee,
+        }
+    }
+}
+
+impl From<FeeOverrides> for GasStrategy {
+    fn from(fees: FeeOverrides) -> Self {
+        GasStrategy::Eip1559 {
+            max_fee: fees.max_fee_per_gas,
+            priority_fee: fees.max_priority_fee_per_gas,
+        }
+    }
+}
+
+/// What a batch of signing requests does when the user rejects one of them in Frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RejectionPolicy {
+    /// Stops at the first rejection and returns `FrameError::BatchRejected`.
+    #[default]
+    Stop,
+    /// Skips rejected requests and continues with the rest of the batch.
+    Skip,
+}
+
+/// How [`FrameClient::send_batch`](crate::client::FrameClient::send_batch) submits the
+/// transactions of a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BatchMode {
+    /// Waits for the receipt of each transaction before submitting the next one.
+    #[default]
+    Sequential,
+    /// Assigns consecutive nonces up front and submits the transactions back-to-back, without
+    /// waiting for them to be mined.
+    Pipelined,
+}
+
+/// How gas pricing is set on a transaction sent through Frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GasStrategy {
+    /// Leaves gas pricing to the provider and Frame, sending a legacy transaction.
+    #[default]
+    Auto,
+    /// Sends a legacy (type 0) transaction with the given gas price in Wei.
+    Legacy(U256),
+    /// Sends an EIP-1559 (type 2) transaction with the given fee caps in Wei.
+    Eip1559 { max_fee: U256, priority_fee: U256 },
+}
+
+impl GasStrategy {
+    /// Applies the strategy to `tx`, producing a legacy or EIP-1559 typed transaction.
+    pub fn apply(self, tx: TransactionRequest) -> TypedTransaction {
+        match self {
+            GasStrategy::Auto => tx.into(),
+            GasStrategy::Legacy(gas_price) => tx.gas_price(gas_price).into(),
+            GasStrategy::Eip1559 {
+                max_fee,
+                priority_fee,
+            } => {
+                let legacy: TypedTransaction = tx.into();
+                let tx: Eip1559TransactionRequest = legacy.into();
+                tx.max_fee_per_gas(max_fee)
+                    .max_priority_fee_per_gas(priority_fee)
+                    .into()
+            }
+        }
+    }
+}
+
+/// Optional overrides applied to a transaction before it is sent through Frame.
+///
+/// Fields left as `None` are filled in by the provider and Frame. Setting either EIP-1559 fee
+/// sends a type 2 transaction; the other one is then estimated if unset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SendOptions {
+    /// The gas limit of the transaction.
+    pub gas_limit: Option<U256>,
+    /// The nonce of the transaction, e.g. to replace a stuck transaction.
+    pub nonce: Option<U256>,
+    /// The gas price of a legacy transaction, in Wei.
+    pub gas_price: Option<U256>,
+    /// The maximum total fee per gas of an EIP-1559 transaction, in Wei.
+    pub max_fee_per_gas: Option<U256>,
+    /// The maximum priority fee per gas of an EIP-1559 transaction, in Wei.
+    pub max_priority_fee_per_gas: Option<U256>,
+}
+
+impl SendOptions {
+    /// Applies the overrides to `tx`, producing a legacy or EIP-1559 typed transaction.
+    ///
+    /// # Errors
+    /// Returns an error if `gas_price` is combined with an EIP-1559 fee.
+    pub fn apply(self, tx: TransactionRequest) -> Result<TypedTransaction> {
+        let eip1559 = self.max_fee_per_gas.is_some() || self.max_priority_fee_per_gas.is_some();
+        ensure!(
+            !(eip1559 && self.gas_price.is_some()),
+            "gas_price can't be combined with EIP-1559 fees"
+        );
+
+        let mut tx: TypedTransaction = if eip1559 {
+            let legacy: TypedTransaction = tx.into();
+            let mut tx: Eip1559TransactionRequest = legacy.into();
+            tx.max_fee_per_gas = self.max_fee_per_gas;
+            tx.max_priority_fee_per_gas = self.max_priority_fee_per_gas;
+            tx.into()
+        } else {
+            let mut tx = tx;
+            tx.gas_price = self.gas_price.or(tx.gas_price);
+            tx.into()
+        };
+        if let Some(gas_limit) = self.gas_limit {
+            tx.set_gas(gas_limit);
+        }
+        if let Some(nonce) = self.nonce {
+            tx.set_nonce(nonce);
+        }
+        Ok(tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_chain_config_serialization() {
+        let config = ChainConfig {
+            chain_id: U256::from(10),
+            chain_name: "OP Mainnet".to_string(),
+            native_currency: NativeCurrency {
+                name: "Ether".to_string(),
+                symbol: "ETH".to_string(),
+                decimals: 18,
+            },
+            rpc_urls: vec!["https://mainnet.optimism.io".to_string()],
+            block_explorer_urls: Some(vec!["https://optimistic.etherscan.io".to_string()]),
+            icon_urls: None,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            json!({
+                "chainId": "0xa",
+                "chainName": "OP Mainnet",
+                "nativeCurrency": { "name": "Ether", "symbol": "ETH", "decimals": 18 },
+                "rpcUrls": ["https://mainnet.optimism.io"],
+                "blockExplorerUrls": ["https://optimistic.etherscan.io"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_gas_strategy_transaction_type() {
+        let tx = TransactionRequest::new().value(U256::from(1));
+
+        let auto = serde_json::to_value(GasStrategy::Auto.apply(tx.clone())).unwrap();
+        assert_eq!(auto["type"], "0x00");
+        assert!(auto.get("gasPrice").is_none());
+
+        let legacy =
+            serde_json::to_value(GasStrategy::Legacy(U256::from(7)).apply(tx.clone())).unwrap();
+        assert_eq!(legacy["type"], "0x00");
+        assert_eq!(legacy["gasPrice"], "0x7");
+
+        let strategy = GasStrategy::Eip1559 {
+            max_fee: U256::from(100),
+            priority_fee: U256::from(2),
+        };
+        let eip1559 = serde_json::to_value(strategy.apply(tx)).unwrap();
+        assert_eq!(eip1559["type"], "0x02");
+        assert_eq!(eip1559["maxFeePerGas"], "0x64");
+        assert_eq!(eip1559["maxPriorityFeePerGas"], "0x2");
+        assert_eq!(eip1559["value"], "0x1");
+    }
+}